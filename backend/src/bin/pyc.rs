@@ -0,0 +1,191 @@
+//! # Polycentrics CLI
+//! Tools around the game logic, which do not need a browser.
+
+use polycentrics_backend::prelude as pyc;
+use std::{env, fs, process};
+
+const USAGE: &str = "\
+usage:
+    pyc book generate <steps> <depth> <file>
+    pyc book lookup <file> [tile...]
+    pyc puzzle generate <opening> <depth> <margin> <file>
+    pyc puzzle check <file>
+    pyc svg [--numbers] [--capture] <file> [tile...]
+    pyc replay [--seconds <seconds>] <file> [tile...]
+    pyc replay --frames <dir> [tile...]
+
+tiles in notation, like R2u or L3d, or as index into the options left";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["book", "generate", steps, depth, file] => generate(steps, depth, file),
+        ["book", "lookup", file, tiles @ ..] => lookup(file, tiles),
+        ["puzzle", "generate", opening, depth, margin, file] => {
+            puzzles(opening, depth, margin, file)
+        }
+        ["puzzle", "check", file] => check(file),
+        ["svg", rest @ ..] => svg(rest),
+        ["replay", "--frames", dir, tiles @ ..] => frames(dir, tiles),
+        ["replay", "--seconds", seconds, file, tiles @ ..] => animate(seconds, file, tiles),
+        ["replay", file, tiles @ ..] => animate("1", file, tiles),
+        _ => Err(USAGE.to_string()),
+    };
+
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+/// Generate an opening book for the default board and write it to `file`.
+fn generate(steps: &str, depth: &str, file: &str) -> Result<(), String> {
+    let steps: u8 = steps
+        .parse()
+        .map_err(|_| format!("invalid steps: {}", steps))?;
+    let depth: u8 = depth
+        .parse()
+        .map_err(|_| format!("invalid depth: {}", depth))?;
+
+    let book = pyc::Book::generate(&pyc::Board::default(), steps, depth);
+    fs::write(file, book.to_bytes()).map_err(|err| format!("{}: {}", file, err))?;
+
+    println!("{} positions written to {}", book.len(), file);
+    Ok(())
+}
+
+/// Print the best known tile after playing `tiles` on the default board.
+fn lookup(file: &str, tiles: &[&str]) -> Result<(), String> {
+    let bytes = fs::read(file).map_err(|err| format!("{}: {}", file, err))?;
+    let book = pyc::Book::from_bytes(&bytes).map_err(|err| format!("{}: {}", file, err))?;
+
+    let board = record(tiles)?.replay().map_err(|err| err.to_string())?;
+
+    match book.get(&board) {
+        // a corrupt book may name a tile that is not left
+        Some(entry) => match board.options().get(entry.tile as usize) {
+            // the options are not rotated yet
+            Some(tile) => println!(
+                "best tile {} ({}), eval {} at depth {}",
+                tile.shape(pyc::Direction::North),
+                entry.tile,
+                entry.eval,
+                entry.depth
+            ),
+            None => return Err(format!("{}: invalid tile {}", file, entry.tile)),
+        },
+        None => println!("position not in book"),
+    }
+
+    Ok(())
+}
+
+/// Mine puzzles from games of the bot on the default board and write them to `file`.
+fn puzzles(opening: &str, depth: &str, margin: &str, file: &str) -> Result<(), String> {
+    let opening: u8 = opening
+        .parse()
+        .map_err(|_| format!("invalid opening: {}", opening))?;
+    let depth: u8 = depth
+        .parse()
+        .map_err(|_| format!("invalid depth: {}", depth))?;
+    // without a margin, another tile may capture as much as the solution
+    let margin: u8 = margin
+        .parse()
+        .ok()
+        .filter(|margin| *margin > 0)
+        .ok_or_else(|| format!("invalid margin, must be at least 1: {}", margin))?;
+
+    let puzzles = pyc::Puzzle::mine(&pyc::Record::default(), opening, depth, margin);
+    let text: String = puzzles
+        .iter()
+        .map(|puzzle| format!("{}\n", puzzle.encode()))
+        .collect();
+    fs::write(file, text).map_err(|err| format!("{}: {}", file, err))?;
+
+    println!("{} puzzles written to {}", puzzles.len(), file);
+    Ok(())
+}
+
+/// Check that every puzzle of `file` can be solved.
+fn check(file: &str) -> Result<(), String> {
+    let text = fs::read_to_string(file).map_err(|err| format!("{}: {}", file, err))?;
+    let puzzles = pyc::Puzzle::decode_all(&text)
+        .map_err(|(line, err)| format!("{}:{}: {}", file, line, err))?;
+
+    println!("{} puzzles in {} can be solved", puzzles.len(), file);
+    Ok(())
+}
+
+/// Write the SVG of the default board after playing `tiles`.
+fn svg(args: &[&str]) -> Result<(), String> {
+    let mut style = pyc::SvgStyle::default();
+    let mut args = args;
+
+    // leading flags, then the positional arguments
+    while let Some((flag, rest)) = args.split_first() {
+        match *flag {
+            "--numbers" => style.move_numbers = true,
+            "--capture" => style.highlight_capture = true,
+            _ => break,
+        }
+
+        args = rest;
+    }
+
+    let (file, tiles) = args.split_first().ok_or_else(|| USAGE.to_string())?;
+    let board = record(tiles)?.replay().map_err(|err| err.to_string())?;
+
+    fs::write(file, board.svg(&style)).map_err(|err| format!("{}: {}", file, err))
+}
+
+/// Write the animated SVG of the game of `tiles` on the default board.
+fn animate(seconds: &str, file: &str, tiles: &[&str]) -> Result<(), String> {
+    let seconds: f32 = seconds
+        .parse()
+        .map_err(|_| format!("invalid seconds: {}", seconds))?;
+
+    let svg = record(tiles)?
+        .animated_svg(&pyc::SvgStyle::default(), seconds)
+        .map_err(|err| err.to_string())?;
+    fs::write(file, svg).map_err(|err| format!("{}: {}", file, err))
+}
+
+/// Write one SVG per step of the game of `tiles` on the default board into `dir`.
+fn frames(dir: &str, tiles: &[&str]) -> Result<(), String> {
+    let frames = record(tiles)?
+        .frames(&pyc::SvgStyle::default())
+        .map_err(|err| err.to_string())?;
+    fs::create_dir_all(dir).map_err(|err| format!("{}: {}", dir, err))?;
+
+    for (i, frame) in frames.iter().enumerate() {
+        let file = format!("{}/frame-{:03}.svg", dir, i);
+        fs::write(&file, frame).map_err(|err| format!("{}: {}", file, err))?;
+    }
+
+    println!("{} frames written to {}", frames.len(), dir);
+    Ok(())
+}
+
+/// Record tiles on the default board, in notation or as indices.
+fn record(tiles: &[&str]) -> Result<pyc::Record, String> {
+    let mut record = pyc::Record::default();
+    let mut board = record.start();
+
+    for tile in tiles {
+        let action = match tile.parse::<u8>() {
+            Ok(index) => pyc::Action::Tile(index),
+            Err(_) => board
+                .parse_tile(tile)
+                .map_err(|err| format!("invalid tile {}: {}", tile, err))?,
+        };
+
+        board
+            .act(action)
+            .map_err(|err| format!("invalid tile {}: {}", tile, err))?;
+        record.moves.push(action);
+    }
+
+    Ok(record)
+}
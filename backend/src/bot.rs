@@ -0,0 +1,49 @@
+pub(crate) mod book;
+pub(crate) mod search;
+
+use crate::game::board::Board;
+use book::Book;
+use search::Search;
+
+/// A virtual player choosing tiles on a [`Board`].
+// a trait and not an enum, so the frontend and server
+// can bring their own players (network, worker, ...)
+pub trait Agent {
+    /// Choose the index of a tile in [`Board::options()`],
+    /// or `None` if there is nothing left to choose.
+    fn choose(&mut self, board: &Board) -> Option<usize>;
+}
+
+/// The built-in virtual player.
+/// It asks the opening [`Book`] first and only searches
+/// positions the book does not know.
+#[derive(Clone)]
+pub struct Bot {
+    pub search: Search,
+    pub book: Option<Book>,
+}
+
+impl Bot {
+    /// Create a bot searching `depth` steps ahead, without a book.
+    pub fn new(depth: u8) -> Self {
+        Self {
+            search: Search { depth },
+            book: None,
+        }
+    }
+}
+
+impl Agent for Bot {
+    fn choose(&mut self, board: &Board) -> Option<usize> {
+        // the book is only a shortcut, so fall back to the search,
+        // and entries of deeper searches would make weak bots too strong
+        self.book
+            .as_ref()
+            .filter(|book| {
+                book.get(board)
+                    .is_some_and(|entry| entry.depth <= self.search.depth)
+            })
+            .and_then(|book| book.best(board))
+            .or_else(|| self.search.best(board).map(|(tile, _)| tile))
+    }
+}
@@ -0,0 +1,307 @@
+use super::search::{Eval, Search};
+use crate::game::{
+    board::{Board, Player},
+    curve::Curve,
+};
+use std::fmt;
+
+/// Opening book of evaluated positions, keyed by [`Book::key`].
+// not a `HashMap`, because the entries are sorted anyway when
+// read from bytes, and a binary search is fast enough
+#[derive(Clone, Default)]
+pub struct Book {
+    entries: Vec<Entry>,
+}
+
+/// Best known move of a position.
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub key: u64,
+    // index into `Board::options()` of the position
+    pub tile: u8,
+    // search depth the evaluation was computed with
+    pub depth: u8,
+    pub eval: i16,
+}
+
+/// Errors reading a [`Book`] from bytes.
+#[derive(Debug)]
+pub enum BookError {
+    Magic,
+    Version(u8),
+    Truncated,
+}
+
+// # file format
+// all numbers are little endian
+// header: magic `PYCB`, version `u8`, number of entries `u32`
+// entry: key `u64`, tile `u8`, depth `u8`, eval `i16`
+const MAGIC: &[u8; 4] = b"PYCB";
+// 2 hashes the arrow into the keys
+const VERSION: u8 = 2;
+const HEADER: usize = 9;
+const ENTRY: usize = 12;
+
+impl Book {
+    /// Evaluate every position reachable within the first `steps` steps of `board`.
+    pub fn generate(board: &Board, steps: u8, depth: u8) -> Self {
+        let search = Search { depth };
+        let mut book = Self::default();
+
+        book.explore(board, steps, &search);
+        book.entries.sort_by_key(|entry| entry.key);
+        book.entries.dedup_by_key(|entry| entry.key);
+
+        book
+    }
+
+    fn explore(&mut self, board: &Board, steps: u8, search: &Search) {
+        if steps == 0 {
+            return;
+        }
+
+        if let Some((tile, eval)) = search.best(board) {
+            self.entries.push(Entry {
+                key: Self::key(board),
+                tile: tile as u8,
+                depth: search.depth,
+                eval: eval.clamp(i16::MIN as Eval, i16::MAX as Eval) as i16,
+            });
+
+            for tile in 0..board.options().len() {
+                let mut next = board.clone();
+                next.step(tile);
+                self.explore(&next, steps - 1, search);
+            }
+        }
+    }
+
+    /// Look up the entry of a position.
+    pub fn get(&self, board: &Board) -> Option<&Entry> {
+        let key = Self::key(board);
+
+        self.entries
+            .binary_search_by_key(&key, |entry| entry.key)
+            .ok()
+            .map(|i| &self.entries[i])
+    }
+
+    /// Best known tile of [`Board::options()`], if the position is in the book.
+    pub fn best(&self, board: &Board) -> Option<usize> {
+        self.get(board)
+            .map(|entry| entry.tile as usize)
+            // guard against books of other tile sets with equal keys
+            .filter(|&tile| tile < board.options().len())
+    }
+
+    /// Number of positions in the book.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Test if the book holds no positions.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Stable hash of a position, the same on every platform.
+    /// [FNV-1a](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function)
+    // not `std::hash`, because `DefaultHasher` may change between releases,
+    // which would invalidate every book file
+    pub fn key(board: &Board) -> u64 {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        let mut write = |byte: u8| {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        };
+
+        write(board.active as u8);
+
+        // boards with an empty path differ only in the start arrow
+        write(board.arrow.pos.x as u8);
+        write(board.arrow.pos.y as u8);
+        write(board.arrow.dir as u8);
+
+        // the path decides all future intersections
+        for curve in board.path.iter().chain(board.tiles.iter()) {
+            for coord in curve_coords(curve).iter() {
+                write(*coord as u8);
+            }
+        }
+
+        // separate tiles from points, and encode the board size
+        write(board.tiles.len() as u8);
+        write(board.points.len() as u8);
+
+        for point in board.points.iter().flatten() {
+            write(match point {
+                None => 0,
+                Some(Player::Gamma) => 1,
+                Some(Player::Delta) => 2,
+            });
+        }
+
+        hash
+    }
+
+    /// Encode the book into its file format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER + self.entries.len() * ENTRY);
+
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+
+        for entry in self.entries.iter() {
+            bytes.extend_from_slice(&entry.key.to_le_bytes());
+            bytes.push(entry.tile);
+            bytes.push(entry.depth);
+            bytes.extend_from_slice(&entry.eval.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    /// Decode a book from its file format.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BookError> {
+        if bytes.len() < HEADER {
+            return Err(BookError::Truncated);
+        }
+
+        if &bytes[..4] != MAGIC {
+            return Err(BookError::Magic);
+        }
+
+        if bytes[4] != VERSION {
+            return Err(BookError::Version(bytes[4]));
+        }
+
+        let len = u32::from_le_bytes([bytes[5], bytes[6], bytes[7], bytes[8]]) as usize;
+        let body = &bytes[HEADER..];
+
+        if body.len() < len * ENTRY {
+            return Err(BookError::Truncated);
+        }
+
+        let mut entries: Vec<Entry> = body
+            .chunks_exact(ENTRY)
+            .take(len)
+            .map(|chunk| {
+                let mut key = [0; 8];
+                key.copy_from_slice(&chunk[..8]);
+
+                Entry {
+                    key: u64::from_le_bytes(key),
+                    tile: chunk[8],
+                    depth: chunk[9],
+                    eval: i16::from_le_bytes([chunk[10], chunk[11]]),
+                }
+            })
+            .collect();
+
+        // files written by hand or other tools may not be sorted
+        entries.sort_by_key(|entry| entry.key);
+
+        Ok(Self { entries })
+    }
+}
+
+fn curve_coords(curve: &Curve) -> [i8; 6] {
+    [
+        curve.start.x,
+        curve.start.y,
+        curve.mid.x,
+        curve.mid.y,
+        curve.end.x,
+        curve.end.y,
+    ]
+}
+
+impl fmt::Display for BookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BookError::Magic => write!(f, "not an opening book"),
+            BookError::Version(version) => write!(f, "unknown book version {}", version),
+            BookError::Truncated => write!(f, "book is truncated"),
+        }
+    }
+}
+
+impl std::error::Error for BookError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::prelude::*;
+
+    #[test]
+    fn key_hashes_the_start_arrow() {
+        let board = Board::default();
+        let mut moved = Board::default();
+        moved.arrow.pos = Vec2D { x: 4, y: 5 };
+        let mut turned = Board::default();
+        turned.arrow.dir = Direction::East;
+
+        assert_ne!(Book::key(&board), Book::key(&moved));
+        assert_ne!(Book::key(&board), Book::key(&turned));
+    }
+
+    fn book() -> Book {
+        let board = Board::default();
+
+        Book {
+            entries: vec![Entry {
+                key: Book::key(&board),
+                tile: 3,
+                depth: 2,
+                eval: -7,
+            }],
+        }
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let book = Book::from_bytes(&book().to_bytes()).unwrap();
+        let entry = book.get(&Board::default()).unwrap();
+
+        assert_eq!(book.len(), 1);
+        assert_eq!((entry.tile, entry.depth, entry.eval), (3, 2, -7));
+    }
+
+    #[test]
+    fn bad_bytes_are_rejected() {
+        let bytes = book().to_bytes();
+
+        assert!(matches!(
+            Book::from_bytes(&bytes[..HEADER - 1]),
+            Err(BookError::Truncated)
+        ));
+        assert!(matches!(
+            Book::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(BookError::Truncated)
+        ));
+
+        let mut magic = bytes.clone();
+        magic[0] = b'X';
+        assert!(matches!(Book::from_bytes(&magic), Err(BookError::Magic)));
+
+        let mut version = bytes;
+        version[4] = VERSION + 1;
+        assert!(matches!(
+            Book::from_bytes(&version),
+            Err(BookError::Version(v)) if v == VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn best_rejects_tiles_out_of_range() {
+        let mut book = book();
+        let board = Board::default();
+
+        assert_eq!(book.best(&board), Some(3));
+
+        book.entries[0].tile = board.options().len() as u8;
+        assert!(book.get(&board).is_some());
+        assert_eq!(book.best(&board), None);
+    }
+}
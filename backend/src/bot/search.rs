@@ -0,0 +1,148 @@
+use crate::game::board::{Board, Player, State};
+
+/// Evaluation of a position in points,
+/// from the view of the active player.
+pub type Eval = i32;
+
+/// Depth limited [negamax](https://en.wikipedia.org/wiki/Negamax)
+/// search with alpha-beta pruning over [`Board::step`].
+#[derive(Clone)]
+pub struct Search {
+    // number of steps to look ahead, zero only evaluates the options
+    pub depth: u8,
+}
+
+impl Search {
+    /// Find the best tile of [`Board::options()`] together with its evaluation.
+    /// Returns `None` if there are no options left.
+    pub fn best(&self, board: &Board) -> Option<(usize, Eval)> {
+        let mut best: Option<(usize, Eval)> = None;
+        let mut alpha = -Eval::MAX;
+
+        for tile in 0..board.options().len() {
+            let mut next = board.clone();
+            next.step(tile);
+
+            let eval = -self.negamax(&next, self.depth, -Eval::MAX, -alpha);
+
+            // keep the first tile on equal evaluations, so the result is stable
+            if best.is_none_or(|(_, best)| eval > best) {
+                best = Some((tile, eval));
+                alpha = alpha.max(eval);
+            }
+        }
+
+        best
+    }
+
+    /// Evaluate every tile of [`Board::options()`], in the order of the options.
+    // no pruning between the options, as every evaluation must be exact
+    pub fn evaluate(&self, board: &Board) -> Vec<(usize, Eval)> {
+        (0..board.options().len())
+            .map(|tile| {
+                let mut next = board.clone();
+                next.step(tile);
+
+                (
+                    tile,
+                    -self.negamax(&next, self.depth, -Eval::MAX, Eval::MAX),
+                )
+            })
+            .collect()
+    }
+
+    /// The principal variation: the best tile of [`Board::options()`],
+    /// then the best answer to it and so on, as deep as the search looks.
+    // searched again after every tile, each time one step less deep
+    pub fn line(&self, board: &Board) -> Vec<usize> {
+        let mut board = board.clone();
+        let mut line = Vec::new();
+
+        for depth in (0..=self.depth).rev() {
+            match (Search { depth }).best(&board) {
+                Some((tile, _)) => {
+                    board.step(tile);
+                    line.push(tile);
+                }
+                None => break,
+            }
+        }
+
+        line
+    }
+
+    fn negamax(&self, board: &Board, depth: u8, mut alpha: Eval, beta: Eval) -> Eval {
+        if depth == 0 || board.options().is_empty() {
+            return evaluate(board);
+        }
+
+        let mut best = -Eval::MAX;
+
+        for tile in 0..board.options().len() {
+            let mut next = board.clone();
+            next.step(tile);
+
+            best = best.max(-self.negamax(&next, depth - 1, -beta, -alpha));
+            alpha = alpha.max(best);
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best
+    }
+}
+
+/// Bonus of the winner in an [`Eval`],
+/// more than any score difference, the board has at most `u8 x u8` points.
+pub const WIN: Eval = 1 << 16;
+
+/// Static evaluation of a [`Board`]: the score difference for the active player,
+/// with a bonus for the winner once the game is decided.
+pub fn evaluate(board: &Board) -> Eval {
+    let diff = board.score.gamma as Eval - board.score.delta as Eval;
+
+    let gamma = match board.state {
+        State::Victory(Player::Gamma) => WIN + diff,
+        State::Victory(Player::Delta) => -WIN + diff,
+        State::Pending | State::Draw => diff,
+    };
+
+    match board.active {
+        Player::Gamma => gamma,
+        Player::Delta => -gamma,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::prelude::*;
+
+    #[test]
+    fn best_captures_points() {
+        let mut board = Board::default();
+        board.step(0);
+        board.step(0);
+
+        let (tile, eval) = (Search { depth: 1 }).best(&board).unwrap();
+        board.step(tile);
+
+        assert_eq!(eval, 1);
+        assert_eq!(board.score.gamma, 1);
+    }
+
+    #[test]
+    fn best_stays_on_the_board() {
+        let mut board = Board::default();
+        board.arrow.pos = Vec2D { x: 0, y: 5 };
+        let leaving = board.find("L1u".parse().unwrap()).unwrap();
+
+        let (tile, eval) = (Search { depth: 1 }).best(&board).unwrap();
+
+        assert_ne!(tile, leaving);
+        assert!(eval > -WIN);
+        assert_eq!((Search { depth: 1 }).evaluate(&board)[leaving].1, -WIN);
+    }
+}
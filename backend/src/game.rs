@@ -1,5 +1,9 @@
+pub(crate) mod action;
 pub(crate) mod board;
+pub(crate) mod clock;
 pub(crate) mod curve;
+pub(crate) mod puzzle;
+pub(crate) mod record;
 
 use super::Float;
 
@@ -0,0 +1,87 @@
+use super::board::{Board, Player, State};
+use std::fmt;
+
+/// Everything a player can do: set a tile, or end the game without one.
+// the player is part of the actions besides tiles, because those
+// can be done out of turn, while tiles always belong to `Board::active`
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Action {
+    /// Index into [`Board::options()`].
+    Tile(u8),
+    /// Give up, the opponent wins.
+    Resign(Player),
+    /// Propose to end in a [`State::Draw`], until the opponent answers or steps.
+    OfferDraw(Player),
+    /// Agree to the draw offered by the opponent.
+    AcceptDraw(Player),
+    /// Refuse the draw offered by the opponent.
+    DeclineDraw(Player),
+}
+
+/// Reasons an [`Action`] can't be done on a [`Board`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionError {
+    Over,
+    NoTile(u8),
+    // a draw was already offered
+    Offered,
+    // no draw was offered by the opponent
+    NoOffer,
+}
+
+impl Board {
+    /// Do an [`Action`], nothing changes on error.
+    pub fn act(&mut self, action: Action) -> Result<(), ActionError> {
+        if !matches!(self.state, State::Pending) {
+            return Err(ActionError::Over);
+        }
+
+        match action {
+            Action::Tile(tile) => {
+                if tile as usize >= self.options().len() {
+                    return Err(ActionError::NoTile(tile));
+                }
+
+                self.step(tile as usize);
+            }
+            Action::Resign(player) => {
+                self.state = State::Victory(player.opponent());
+                self.draw_offer = None;
+            }
+            Action::OfferDraw(player) => {
+                if self.draw_offer.is_some() {
+                    return Err(ActionError::Offered);
+                }
+
+                self.draw_offer = Some(player);
+            }
+            Action::AcceptDraw(player) | Action::DeclineDraw(player) => {
+                if self.draw_offer != Some(player.opponent()) {
+                    return Err(ActionError::NoOffer);
+                }
+
+                self.draw_offer = None;
+
+                if let Action::AcceptDraw(_) = action {
+                    self.state = State::Draw;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionError::Over => write!(f, "the game is over"),
+            ActionError::NoTile(tile) => write!(f, "no tile {}", tile),
+            ActionError::Offered => write!(f, "a draw was already offered"),
+            ActionError::NoOffer => write!(f, "no draw was offered"),
+        }
+    }
+}
+
+impl std::error::Error for ActionError {}
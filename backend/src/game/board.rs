@@ -1,4 +1,7 @@
-use super::curve::{Curve, Intersection, Path};
+use super::{
+    action::Action,
+    curve::{Curve, Intersection, NotationError, Path, Shape},
+};
 use crate::{
     game::{CONVEX_2X1, CONVEX_3X2, DELTA},
     math::prelude::*,
@@ -8,6 +11,7 @@ use crate::{
 /// Subject of the game is the [`Board`].
 /// It holds the current state and all data.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Board {
     // not computed because modulus is high complexity
     // must be chosen at beginning
@@ -35,10 +39,15 @@ pub struct Board {
     // same as state
     // not in state because data is always the same, no matter the state
     pub score: Score,
+    // not computed because the polygons of old steps are gone
+    pub captures: Vec<Capture>,
+    // player waiting for an answer to its draw offer
+    pub draw_offer: Option<Player>,
 }
 
 /// The pointer where the next tile will be appended.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Arrow {
     // i8, because there are out of border moves
     pub pos: Vec2D<i8>,
@@ -48,7 +57,8 @@ pub struct Arrow {
 /// Enum of possible players.
 /// [`Player::Gamma`] inspired by GAMMAGRAPHICS.
 // is not player id as u8 because handling of draws and out of border moves
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Player {
     Gamma,
     Delta,
@@ -59,12 +69,23 @@ pub enum Player {
 /// of methods called on the [`Board`].
 // `Board` itself is not an enum because data is the same no matter the state
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum State {
     Victory(Player),
     Pending,
     Draw,
 }
 
+impl Player {
+    /// The other player.
+    pub fn opponent(self) -> Self {
+        match self {
+            Player::Gamma => Player::Delta,
+            Player::Delta => Player::Gamma,
+        }
+    }
+}
+
 /// Each player holds an score according
 /// to how much points he "collects".
 /// Nevertheless this does not have to decide
@@ -72,10 +93,24 @@ pub enum State {
 /// invalid move.
 // not an union because of high complexity
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Score {
     // field of max `u8 x u8 = u64 <=> 2^8*2^8 = 2^8^2 = 2^64`
-    gamma: u64,
-    delta: u64,
+    pub gamma: u64,
+    pub delta: u64,
+}
+
+/// A region enclosed by the path, and the points it collected.
+// the polygon is kept, so the region can be drawn later
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capture {
+    // step of the tile which closed the region
+    pub step: u8,
+    pub player: Player,
+    pub poly: Path,
+    // number of points collected by this region
+    pub points: u64,
 }
 
 impl Default for Board {
@@ -97,6 +132,8 @@ impl Default for Board {
             points: vec![vec![None; 11]; 11],
             state: State::Pending,
             score: Score { gamma: 0, delta: 0 },
+            captures: Vec::new(),
+            draw_offer: None,
         }
     }
 }
@@ -120,18 +157,54 @@ impl Board {
         }
     }
 
+    /// Index into [`Board::options()`] of a tile of the `shape`, the first of equal ones.
+    /// Indices shift as tiles are set, shapes do not.
+    pub fn find(&self, shape: Shape) -> Option<usize> {
+        self.options()
+            .iter()
+            .position(|tile| tile.shape(Direction::North) == shape)
+    }
+
+    /// The [`Action`] setting the tile of a notation like `R2u`, see [`Shape`].
+    pub fn parse_tile(&self, notation: &str) -> Result<Action, NotationError> {
+        let shape = notation.parse()?;
+        let tile = self.find(shape).ok_or(NotationError::Missing)?;
+
+        Ok(Action::Tile(tile as u8))
+    }
+
     /// Step by choosing a tile.
     /// Panics if index on remaining tiles ([`Board::options()`]) is out of bounds.
     pub fn step(&mut self, tile: usize) {
         self.set_tile(tile);
         self.update_score();
+        self.update_state();
+
+        // stepping instead of answering declines a draw offer
+        self.draw_offer = None;
 
         // increase step
         self.step += 1;
         // switch players
-        self.active = match self.active {
-            Player::Gamma => Player::Delta,
-            Player::Delta => Player::Gamma,
+        self.active = self.active.opponent();
+    }
+
+    /// End the game after a step of the active player.
+    // same rules as the prototype, see `prototype/löve_2d/main.lua`
+    fn update_state(&mut self) {
+        let size = self.points.len() as i8;
+        let pos = self.arrow.pos;
+
+        // the arrow left the board, the move was invalid
+        if pos.x < 0 || pos.y < 0 || pos.x >= size || pos.y >= size {
+            self.state = State::Victory(self.active.opponent());
+        } else if self.tiles.is_empty() {
+            // a tie goes to Delta, to make up for moving second
+            self.state = if self.score.gamma > self.score.delta {
+                State::Victory(Player::Gamma)
+            } else {
+                State::Victory(Player::Delta)
+            };
         }
     }
 
@@ -139,7 +212,7 @@ impl Board {
     // to understand what happens, have a look at this (deprecated since v0.5.0): [GeoGebra PYC](https://www.geogebra.org/calculator/qp8gjrsz)
     fn set_tile(&mut self, tile: usize) {
         // removes and returns the tile, panics if the index is out of bounds
-        let mut tile = self.tiles.remove(tile as usize);
+        let mut tile = self.tiles.remove(tile);
 
         let new_dir = self.arrow.dir
             + if tile.end.x > 0 {
@@ -167,7 +240,12 @@ impl Board {
     fn update_score(&mut self) {
         let ints = self.latest_intersections();
         let polys = self.polys(ints);
-        self.check_points(polys); // , points);
+        let captured = self.check_points(polys); // , points);
+
+        match self.active {
+            Player::Gamma => self.score.gamma += captured,
+            Player::Delta => self.score.delta += captured,
+        }
     }
 
     /// Find all new intersections with the last tile and the path.
@@ -235,7 +313,7 @@ impl Board {
             let last: Vec<Vec2D<Float>> = self.path.last().unwrap().minimal_path();
             poly.extend_from_slice(&last[..(last_t * last.len() as Float).ceil() as usize]);
 
-            poly.push(poly.first().unwrap().clone());
+            poly.push(*poly.first().unwrap());
             polys.push((poly, points));
         }
 
@@ -243,14 +321,20 @@ impl Board {
     }
 
     #[rustfmt::skip]
-    fn check_points(&mut self, polys: Vec<(Path, Vec<Vec2D<i8>>)>) {
+    fn check_points(&mut self, polys: Vec<(Path, Vec<Vec2D<i8>>)>) -> u64 {
         // , points: Vec<Vec2D<i8>>) {
+        // number of newly collected points
+        let mut captured = 0;
+
         for (poly, points) in polys {
+            // number of points collected by this polygon
+            let mut held = 0;
+
             // iterate through all free points. could be optimized with `flatten` and `filter`
             // self.points.iter().enumerate().map(|(i, points)| points.iter().filter(|point| point.is_none()).map(||));
             for (j, column) in self.points.iter_mut().enumerate() {
                 for (i, point) in column.iter_mut().enumerate() {
-                    if let None = point {
+                    if point.is_none() {
                         // the crossing number algorithm does not work for non-simple polys
                         // thats why we have to use the winding number algorithm
                         if winding_number(
@@ -261,6 +345,7 @@ impl Board {
                             &poly,
                         ) != 0 {
                             *point = Some(self.active);
+                            held += 1;
                         }
                     }
                 }
@@ -269,40 +354,45 @@ impl Board {
             for point in points {
                 if point.x >= 0 && point.x < self.points.len() as i8 && point.y >= 0 && point.y < self.points.len() as i8 {
                     let board_score = &mut self.points[point.y as usize][point.x as usize];
-                    if let None = board_score {
-                        if vec![
-                            Vec2D { x: point.x as Float, y: point.y as Float - DELTA, },
-                            Vec2D { x: point.x as Float + DELTA, y: point.y as Float, },
-                            Vec2D { x: point.x as Float, y: point.y as Float + DELTA, },
-                            Vec2D { x: point.x as Float - DELTA, y: point.y as Float, },
-
-                            Vec2D { x: point.x as Float + DELTA, y: point.y as Float - CONVEX_2X1, },
-                            Vec2D { x: point.x as Float + DELTA, y: point.y as Float - CONVEX_3X2, },
-                            Vec2D { x: point.x as Float + CONVEX_2X1, y: point.y as Float - DELTA, },
-                            Vec2D { x: point.x as Float + CONVEX_3X2, y: point.y as Float - DELTA, },
-
-                            Vec2D { x: point.x as Float + DELTA, y: point.y as Float + CONVEX_2X1, },
-                            Vec2D { x: point.x as Float + DELTA, y: point.y as Float + CONVEX_3X2, },
-                            Vec2D { x: point.x as Float + CONVEX_2X1, y: point.y as Float + DELTA, },
-                            Vec2D { x: point.x as Float + CONVEX_3X2, y: point.y as Float + DELTA, },
-
-                            Vec2D { x: point.x as Float - DELTA, y: point.y as Float - CONVEX_2X1, },
-                            Vec2D { x: point.x as Float - DELTA, y: point.y as Float - CONVEX_3X2, },
-                            Vec2D { x: point.x as Float - CONVEX_2X1, y: point.y as Float - DELTA, },
-                            Vec2D { x: point.x as Float - CONVEX_3X2, y: point.y as Float - DELTA, },
-
-                            Vec2D { x: point.x as Float - DELTA, y: point.y as Float + CONVEX_2X1, },
-                            Vec2D { x: point.x as Float - DELTA, y: point.y as Float + CONVEX_3X2, },
-                            Vec2D { x: point.x as Float - CONVEX_2X1, y: point.y as Float + DELTA, },
-                            Vec2D { x: point.x as Float - CONVEX_3X2, y: point.y as Float + DELTA, },
-                        ]
-                        .into_iter()
-                        .any(|variant| winding_number(variant, &poly) != 0) {
-                            *board_score = Some(self.active);
-                        }
+                    if board_score.is_none() && vec![
+                        Vec2D { x: point.x as Float, y: point.y as Float - DELTA, },
+                        Vec2D { x: point.x as Float + DELTA, y: point.y as Float, },
+                        Vec2D { x: point.x as Float, y: point.y as Float + DELTA, },
+                        Vec2D { x: point.x as Float - DELTA, y: point.y as Float, },
+
+                        Vec2D { x: point.x as Float + DELTA, y: point.y as Float - CONVEX_2X1, },
+                        Vec2D { x: point.x as Float + DELTA, y: point.y as Float - CONVEX_3X2, },
+                        Vec2D { x: point.x as Float + CONVEX_2X1, y: point.y as Float - DELTA, },
+                        Vec2D { x: point.x as Float + CONVEX_3X2, y: point.y as Float - DELTA, },
+
+                        Vec2D { x: point.x as Float + DELTA, y: point.y as Float + CONVEX_2X1, },
+                        Vec2D { x: point.x as Float + DELTA, y: point.y as Float + CONVEX_3X2, },
+                        Vec2D { x: point.x as Float + CONVEX_2X1, y: point.y as Float + DELTA, },
+                        Vec2D { x: point.x as Float + CONVEX_3X2, y: point.y as Float + DELTA, },
+
+                        Vec2D { x: point.x as Float - DELTA, y: point.y as Float - CONVEX_2X1, },
+                        Vec2D { x: point.x as Float - DELTA, y: point.y as Float - CONVEX_3X2, },
+                        Vec2D { x: point.x as Float - CONVEX_2X1, y: point.y as Float - DELTA, },
+                        Vec2D { x: point.x as Float - CONVEX_3X2, y: point.y as Float - DELTA, },
+
+                        Vec2D { x: point.x as Float - DELTA, y: point.y as Float + CONVEX_2X1, },
+                        Vec2D { x: point.x as Float - DELTA, y: point.y as Float + CONVEX_3X2, },
+                        Vec2D { x: point.x as Float - CONVEX_2X1, y: point.y as Float + DELTA, },
+                        Vec2D { x: point.x as Float - CONVEX_3X2, y: point.y as Float + DELTA, },
+                    ]
+                    .into_iter()
+                    .any(|variant| winding_number(variant, &poly) != 0) {
+                        *board_score = Some(self.active);
+                        held += 1;
                     }
                 }
             }
+
+            // regions without new points are doubles or already collected
+            if held > 0 {
+                captured += held;
+                self.captures.push(Capture { step: self.step, player: self.active, poly, points: held });
+            }
         }
 
         /* for point in points {
@@ -317,6 +407,8 @@ impl Board {
                 }
             }
         } */
+
+        captured
     }
 }
 
@@ -327,7 +419,7 @@ impl Board {
 // There is no warranty for this code, and the author of it cannot
 // be held liable for any real or imagined damage from its use.
 // Users of this code must verify correctness for their application.
-fn winding_number(point: Vec2D<Float>, poly: &Vec<Vec2D<Float>>) -> i32 {
+fn winding_number(point: Vec2D<Float>, poly: &[Vec2D<Float>]) -> i32 {
     if poly.len() < 3 {
         0
     } else {
@@ -335,18 +427,62 @@ fn winding_number(point: Vec2D<Float>, poly: &Vec<Vec2D<Float>>) -> i32 {
 
         for i in 0..poly.len() - 1 {
             if poly[i].y <= point.y {
-                if poly[i + 1].y > point.y {
-                    if point.is_left(poly[i], poly[i + 1]) > 0.0 {
-                        wn += 1;
-                    }
-                }
-            } else if poly[i + 1].y <= point.y {
-                if point.is_left(poly[i], poly[i + 1]) < 0.0 {
-                    wn -= 1;
+                if poly[i + 1].y > point.y && point.is_left(poly[i], poly[i + 1]) > 0.0 {
+                    wn += 1;
                 }
+            } else if poly[i + 1].y <= point.y && point.is_left(poly[i], poly[i + 1]) < 0.0 {
+                wn -= 1;
             }
         }
 
         wn
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Gamma sets the last tile of the board
+    fn last_step(gamma: u64, delta: u64) -> State {
+        let mut board = Board {
+            tiles: vec!["R1u".parse().unwrap()],
+            score: Score { gamma, delta },
+            ..Board::default()
+        };
+
+        board.step(0);
+        board.state
+    }
+
+    #[test]
+    fn leaving_the_board_loses() {
+        let mut board = Board::default();
+        board.arrow.pos = Vec2D { x: 0, y: 5 };
+
+        let tile = board.find("L1u".parse().unwrap()).unwrap();
+        board.step(tile);
+
+        assert!(board.arrow.pos.x < 0);
+        assert!(matches!(board.state, State::Victory(Player::Delta)));
+    }
+
+    #[test]
+    fn staying_on_the_board_goes_on() {
+        let mut board = Board::default();
+        board.step(0);
+
+        assert!(matches!(board.state, State::Pending));
+    }
+
+    #[test]
+    fn more_points_win_once_the_tiles_run_out() {
+        assert!(matches!(last_step(3, 1), State::Victory(Player::Gamma)));
+        assert!(matches!(last_step(1, 3), State::Victory(Player::Delta)));
+    }
+
+    #[test]
+    fn ties_go_to_the_second_player() {
+        assert!(matches!(last_step(2, 2), State::Victory(Player::Delta)));
+    }
+}
@@ -0,0 +1,265 @@
+use super::{
+    action::{Action, ActionError},
+    board::{Board, Player, State},
+};
+
+/// Milliseconds, of a duration or of a point in time.
+// not `std::time` because `Instant` is not available on `wasm32`
+// and the caller decides where the time comes from (browser, server or a test)
+pub type Millis = u64;
+
+/// Time added to a player after each of its steps.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Increment {
+    None,
+    /// Always add the increment.
+    Fischer(Millis),
+    /// Add back the time used by the step, but at most the increment.
+    Bronstein(Millis),
+}
+
+/// Time each player starts with and its [`Increment`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeControl {
+    pub initial: Millis,
+    pub increment: Increment,
+}
+
+/// A chess clock for both players of a [`Board`].
+/// Every method takes the current time `now`, the clock never reads it.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Clock {
+    pub control: TimeControl,
+    // time left at the last press, not counting the running turn
+    pub gamma: Millis,
+    pub delta: Millis,
+    // player whose time runs, and since when
+    // `None` before the first step and after the end
+    pub running: Option<(Player, Millis)>,
+}
+
+impl TimeControl {
+    /// `minutes` for each player and a Fischer increment of `seconds`.
+    pub fn fischer(minutes: Millis, seconds: Millis) -> Self {
+        Self {
+            initial: minutes * 60_000,
+            increment: Increment::Fischer(seconds * 1000),
+        }
+    }
+
+    /// `minutes` for each player and a Bronstein increment of `seconds`.
+    pub fn bronstein(minutes: Millis, seconds: Millis) -> Self {
+        Self {
+            initial: minutes * 60_000,
+            increment: Increment::Bronstein(seconds * 1000),
+        }
+    }
+}
+
+impl Clock {
+    /// Both players get the initial time, nothing runs yet.
+    pub fn new(control: TimeControl) -> Self {
+        Self {
+            control,
+            gamma: control.initial,
+            delta: control.initial,
+            running: None,
+        }
+    }
+
+    /// Start the time of `player`, the clock of the other one stops.
+    pub fn start(&mut self, player: Player, now: Millis) {
+        self.stop(now);
+        self.running = Some((player, now));
+    }
+
+    /// Stop the running time, without an increment.
+    pub fn stop(&mut self, now: Millis) {
+        if let Some((player, _)) = self.running {
+            *self.time_mut(player) = self.left(player, now);
+            self.running = None;
+        }
+    }
+
+    /// Time left for `player` at `now`.
+    pub fn left(&self, player: Player, now: Millis) -> Millis {
+        let time = match player {
+            Player::Gamma => self.gamma,
+            Player::Delta => self.delta,
+        };
+
+        match self.running {
+            Some((running, since)) if running == player => {
+                time.saturating_sub(now.saturating_sub(since))
+            }
+            _ => time,
+        }
+    }
+
+    /// The player whose time ran out at `now`, if any.
+    pub fn flagged(&self, now: Millis) -> Option<Player> {
+        self.running
+            .map(|(player, _)| player)
+            .filter(|player| self.left(*player, now) == 0)
+    }
+
+    /// End the turn of the running player: add its increment and start the opponent.
+    /// Does nothing if its time already ran out.
+    pub fn press(&mut self, now: Millis) {
+        if let Some((player, since)) = self.running {
+            if self.flagged(now).is_some() {
+                return;
+            }
+
+            let used = now.saturating_sub(since);
+            let left = self.left(player, now);
+
+            *self.time_mut(player) = left
+                + match self.control.increment {
+                    Increment::None => 0,
+                    Increment::Fischer(increment) => increment,
+                    Increment::Bronstein(delay) => used.min(delay),
+                };
+
+            self.running = Some((player.opponent(), now));
+        }
+    }
+
+    /// End the game on the [`Board`] if the time of its active player ran out.
+    /// Returns `true` if it did.
+    // called on every tick of the caller, not only on steps,
+    // because a player might never step again
+    pub fn check(&mut self, board: &mut Board, now: Millis) -> bool {
+        if let State::Pending = board.state {
+            if let Some(player) = self.flagged(now) {
+                board.state = State::Victory(player.opponent());
+                self.stop(now);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Step the [`Board`] and press the clock, unless time ran out before.
+    /// The first step of a game starts the clock: it is not timed and gets no increment,
+    /// so Gamma does not lose time waiting for an opponent to show up.
+    /// Panics like [`Board::step`].
+    pub fn step(&mut self, board: &mut Board, tile: usize, now: Millis) {
+        if self.check(board, now) {
+            return;
+        }
+
+        match self.running {
+            Some(_) => self.press(now),
+            // the first player did not know when the game started
+            None => self.running = Some((board.active.opponent(), now)),
+        }
+
+        board.step(tile);
+
+        if !matches!(board.state, State::Pending) {
+            self.stop(now);
+        }
+    }
+
+    /// Do an [`Action`] on the [`Board`], like [`Clock::step`] for tiles.
+    /// The clock stops if the action ends the game.
+    pub fn act(
+        &mut self,
+        board: &mut Board,
+        action: Action,
+        now: Millis,
+    ) -> Result<(), ActionError> {
+        // tiles would restart the clock of a finished game
+        if self.check(board, now) || !matches!(board.state, State::Pending) {
+            return Err(ActionError::Over);
+        }
+
+        if let Action::Tile(tile) = action {
+            if tile as usize >= board.options().len() {
+                return Err(ActionError::NoTile(tile));
+            }
+
+            self.step(board, tile as usize, now);
+        } else {
+            board.act(action)?;
+
+            if !matches!(board.state, State::Pending) {
+                self.stop(now);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn time_mut(&mut self, player: Player) -> &mut Millis {
+        match player {
+            Player::Gamma => &mut self.gamma,
+            Player::Delta => &mut self.delta,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Gamma steps at 0, which starts the time of Delta
+    fn started(control: TimeControl) -> (Clock, Board) {
+        let mut clock = Clock::new(control);
+        let mut board = Board::default();
+        clock.step(&mut board, 0, 0);
+        (clock, board)
+    }
+
+    #[test]
+    fn fischer_adds_the_increment() {
+        let (mut clock, mut board) = started(TimeControl::fischer(1, 2));
+        clock.step(&mut board, 0, 4000);
+
+        assert_eq!(clock.delta, 58_000);
+        assert_eq!(clock.left(Player::Delta, 10_000), 58_000);
+        assert!(clock.running == Some((Player::Gamma, 4000)));
+    }
+
+    #[test]
+    fn bronstein_adds_at_most_the_delay() {
+        let (mut clock, mut board) = started(TimeControl::bronstein(1, 1));
+        clock.step(&mut board, 0, 4000);
+        assert_eq!(clock.delta, 57_000);
+
+        // a quick step gets back only the time it used
+        clock.step(&mut board, 0, 4500);
+        assert_eq!(clock.gamma, 60_000);
+    }
+
+    #[test]
+    fn flag_falls() {
+        let (mut clock, mut board) = started(TimeControl::fischer(1, 0));
+
+        assert!(!clock.check(&mut board, 59_999));
+        assert!(clock.flagged(60_000) == Some(Player::Delta));
+        assert!(clock.check(&mut board, 60_000));
+        assert!(matches!(board.state, State::Victory(Player::Gamma)));
+        assert!(clock.running.is_none());
+
+        assert!(matches!(
+            clock.act(&mut board, Action::Tile(0), 61_000),
+            Err(ActionError::Over)
+        ));
+    }
+
+    #[test]
+    fn first_step_is_untimed() {
+        let mut clock = Clock::new(TimeControl::fischer(1, 2));
+        let mut board = Board::default();
+        clock.step(&mut board, 0, 30_000);
+
+        assert_eq!(clock.gamma, 60_000);
+        assert!(clock.running == Some((Player::Delta, 30_000)));
+    }
+}
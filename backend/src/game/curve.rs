@@ -11,6 +11,7 @@ use crate::Float;
 // [v0.4.0](https://github.com/m4dh0rs3/pyc/tree/v0.4.0)
 // to keep this type, i can't optimize the curve path!
 #[derive(Clone)] // , PartialEq, Eq)] // is clone, because `Board` must be clone
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Curve {
     // as `Arrow` can move out of border
     // these all lie on a board point, so i8 is sufficient
@@ -30,7 +31,7 @@ pub(crate) type Path = Vec<Vec2D<Float>>;
 pub(crate) type Intersection = (Float, Float);
 
 // axis aligned bounding box: (bottom left, top right)
-type AABB = (Vec2D<Float>, Vec2D<Float>);
+type Aabb = (Vec2D<Float>, Vec2D<Float>);
 
 use super::DETAIL;
 
@@ -79,7 +80,7 @@ impl Curve {
     }
 
     /// Compute axis aligned bounding box assuming curve is y-monotone.
-    pub fn aabb(&self, start: Float, end: Float) -> AABB {
+    pub fn aabb(&self, start: Float, end: Float) -> Aabb {
         // not pre computing the conversions again for only two points
         let start = self.point(start);
         let end = self.point(end);
@@ -146,7 +147,7 @@ impl Curve {
 
             // removed detail function overhead, use const instead
             if n >= DETAIL {
-                return vec![(self_t + next_offset, other_t + next_offset)];
+                vec![(self_t + next_offset, other_t + next_offset)]
             } else {
                 let mut ints = Vec::new();
 
@@ -169,10 +170,45 @@ impl Curve {
 }
 
 // Test if two axis aligned bounding boxes intersect, or lay on two edges.
-fn aabb_intersect(a: AABB, b: AABB) -> bool {
+fn aabb_intersect(a: Aabb, b: Aabb) -> bool {
     a.0.x < b.1.x && a.1.x >= b.0.x && a.0.y < b.1.y && a.1.y >= b.0.y
 }
 
+/// Named sets of tiles a game can be played with.
+// not just a `Vec<Curve>`, so records and the network only carry a name
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TileSet {
+    // up and down, left and right, radii 1 to 3, the original game
+    #[default]
+    Convex4x3,
+    // radii 1 and 2 only, a quick game
+    Convex4x2,
+    // every tile of the original game twice, a long game
+    Double4x3,
+}
+
+impl TileSet {
+    /// Every set, for menus.
+    pub const ALL: [TileSet; 3] = [TileSet::Convex4x3, TileSet::Convex4x2, TileSet::Double4x3];
+
+    /// The tiles of the set, in the order of [`Board::options()`](super::board::Board::options).
+    // only radii up to 3, because the points next to a curve are tested with offsets of those
+    pub fn tiles(self) -> Vec<Curve> {
+        match self {
+            TileSet::Convex4x3 => Curve::convex_4x3(),
+            TileSet::Convex4x2 => Curve::convex_4x3()
+                .into_iter()
+                .filter(|curve| curve.end.x.abs() < 3)
+                .collect(),
+            TileSet::Double4x3 => Curve::convex_4x3()
+                .into_iter()
+                .flat_map(|curve| vec![curve.clone(), curve])
+                .collect(),
+        }
+    }
+}
+
 impl Curve {
     #[rustfmt::skip]
     pub fn convex_4x3() -> Vec<Self> {
@@ -201,17 +237,212 @@ impl Curve {
     }
 }
 
-use std::fmt;
+/// How a tile turns, seen from the arrow it starts at.
+// the parts of a label, so frontends can word it in their language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shape {
+    pub right: bool,
+    // ends ahead of the arrow, not behind it
+    pub forward: bool,
+    pub radius: i8,
+}
+
+impl Curve {
+    /// The [`Shape`] of the curve, set by an arrow facing `dir`.
+    /// Tiles of the set face north.
+    pub fn shape(&self, dir: Direction) -> Shape {
+        // undo the rotation of `Board::step()`, so the tile faces north again
+        let back = match dir {
+            Direction::North => Direction::North,
+            Direction::East => Direction::West,
+            Direction::South => Direction::South,
+            Direction::West => Direction::East,
+        };
+        let end = (self.end + self.start * -1).rotate(back);
+
+        Shape {
+            right: end.x > 0,
+            forward: end.y < 0,
+            radius: end.x.abs(),
+        }
+    }
+}
+
+use std::{fmt, str::FromStr};
+
+// # notation
+// a tile of the set as turn `R`ight or `L`eft, radius,
+// then `u`p if it ends ahead of the arrow or `d`own if behind it
+// example: `R2u`, the tile turning right with radius 2 ending ahead
+
+/// Reasons a tile can't be read from its notation, see [`Shape`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotationError {
+    // not starting with `R` or `L`
+    Turn,
+    // no radius of the tile sets between
+    Radius,
+    // not ending with `u` or `d`
+    Forward,
+    // no tile of the shape is left on the board
+    Missing,
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            if self.right { 'R' } else { 'L' },
+            self.radius,
+            if self.forward { 'u' } else { 'd' }
+        )
+    }
+}
+
+impl FromStr for Shape {
+    type Err = NotationError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let right = match text.chars().next() {
+            Some('R') => true,
+            Some('L') => false,
+            _ => return Err(NotationError::Turn),
+        };
+        let forward = match text.chars().last() {
+            Some('u') if text.len() > 1 => true,
+            Some('d') if text.len() > 1 => false,
+            _ => return Err(NotationError::Forward),
+        };
+
+        // exactly one digit, so no sign or leading zeros
+        let radius = match text.as_bytes() {
+            [_, digit @ b'1'..=b'3', _] => (digit - b'0') as i8,
+            _ => return Err(NotationError::Radius),
+        };
+
+        Ok(Self {
+            right,
+            forward,
+            radius,
+        })
+    }
+}
+
+/// A tile of the set from its notation, starting at the origin facing north.
+impl FromStr for Curve {
+    type Err = NotationError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let shape: Shape = text.parse()?;
+        // the y axis is flipped in screen space
+        let y = if shape.forward { -1 } else { 1 };
+        let x = if shape.right { 1 } else { -1 };
+
+        Ok(Self {
+            start: Vec2D::zero(),
+            mid: Vec2D { x: 0, y } * shape.radius,
+            end: Vec2D { x, y } * shape.radius,
+        })
+    }
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::Turn => write!(f, "the tile must start with R or L"),
+            NotationError::Radius => write!(f, "the radius must be 1 to 3"),
+            NotationError::Forward => write!(f, "the tile must end with u or d"),
+            NotationError::Missing => write!(f, "no such tile left"),
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
 
 impl fmt::Debug for Curve {
     // DEBUG VIEW
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shape = self.shape(Direction::North);
+
         write!(
             f,
             "{} {} {}x",
-            if self.end.x > 0 { "Right" } else { "Left" },
-            if self.mid.y > 0 { "Down" } else { "Up" },
-            self.end.x.abs()
+            if shape.right { "Right" } else { "Left" },
+            if shape.forward { "Up" } else { "Down" },
+            shape.radius
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::Board;
+
+    fn shapes() -> Vec<Shape> {
+        let mut shapes = Vec::new();
+
+        for &right in [false, true].iter() {
+            for &forward in [false, true].iter() {
+                for radius in 1..=3 {
+                    shapes.push(Shape {
+                        right,
+                        forward,
+                        radius,
+                    });
+                }
+            }
+        }
+
+        shapes
+    }
+
+    #[test]
+    fn notation_round_trips() {
+        for shape in shapes() {
+            assert_eq!(shape.to_string().parse(), Ok(shape));
+
+            let curve: Curve = shape.to_string().parse().unwrap();
+            assert_eq!(curve.shape(Direction::North), shape);
+        }
+    }
+
+    #[test]
+    fn shapes_of_the_path_undo_the_rotation() {
+        for dir in [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ]
+        .iter()
+        {
+            for shape in shapes() {
+                let mut board = Board::default();
+                board.arrow.dir = *dir;
+
+                board.step(board.find(shape).unwrap());
+                assert_eq!(board.path[0].shape(*dir), shape);
+            }
+        }
+    }
+
+    #[test]
+    fn malformed_notation_is_rejected() {
+        let err = |text: &str| text.parse::<Shape>().err();
+
+        assert_eq!(err(""), Some(NotationError::Turn));
+        assert_eq!(err("r2u"), Some(NotationError::Turn));
+        assert_eq!(err("X2u"), Some(NotationError::Turn));
+        assert_eq!(err("R"), Some(NotationError::Forward));
+        assert_eq!(err("R2x"), Some(NotationError::Forward));
+        assert_eq!(err("Ru"), Some(NotationError::Radius));
+        assert_eq!(err("R0u"), Some(NotationError::Radius));
+        assert_eq!(err("R4u"), Some(NotationError::Radius));
+        assert_eq!(err("R+1u"), Some(NotationError::Radius));
+        assert_eq!(err("R01u"), Some(NotationError::Radius));
+        assert_eq!(err("R12u"), Some(NotationError::Radius));
+        assert_eq!(err("R²u"), Some(NotationError::Radius));
+    }
+}
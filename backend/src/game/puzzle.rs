@@ -0,0 +1,299 @@
+use super::{
+    action::{Action, ActionError},
+    board::{Board, Player, State},
+    record::{CodeError, Record},
+};
+use crate::{bot::search::Search, math::prelude::*};
+use std::fmt;
+
+/// A position with a best tile to find, and the answers to it.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Puzzle {
+    // the position, as the game leading to it
+    pub record: Record,
+    // indices into `Board::options()` from the position on:
+    // the tile of the solver, then the answer and the next tile, and so on
+    pub solution: Vec<u8>,
+    // captured by the tiles of the solver, the goal shown to it
+    pub points: u64,
+}
+
+/// Reasons a puzzle can't be read or solved, see [`Puzzle::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleError {
+    Code(CodeError),
+    // the line ends after the code
+    Missing,
+    // malformed or missing tile at the index of the solution
+    Tile(usize),
+    // the game ends before the last tile of the solution
+    Over,
+    // the solution ends with an answer, not with a tile of the solver
+    Answer,
+    // another first tile is as good as the one of the solution
+    Ambiguous,
+    // the points are missing or not the ones the solution captures
+    Points,
+}
+
+// # puzzle file
+// one puzzle per line, empty lines and lines starting with `#` are skipped
+// fields separated by a space: link code of the record, see `Record::encode`,
+// solution with tiles as one base 36 digit, points captured by the solver
+// example: `1.11.5.5.n.0.3a7 2 5`
+
+impl Puzzle {
+    /// The [`Board`] to solve.
+    /// Fails on the first move of the record that can't be done.
+    pub fn start(&self) -> Result<Board, ActionError> {
+        self.record.replay()
+    }
+
+    /// Check the solution by replaying it with [`Board::step`],
+    /// and that a search as deep as the solution finds no other first tile as good.
+    pub fn validate(&self) -> Result<(), PuzzleError> {
+        if self.solution.len().is_multiple_of(2) {
+            return Err(PuzzleError::Answer);
+        }
+
+        let start = self
+            .start()
+            .map_err(|_| PuzzleError::Code(CodeError::Invalid))?;
+        let mut board = start.clone();
+
+        for (i, tile) in self.solution.iter().enumerate() {
+            if !matches!(board.state, State::Pending) {
+                return Err(PuzzleError::Over);
+            }
+            if *tile as usize >= board.options().len() {
+                return Err(PuzzleError::Tile(i));
+            }
+
+            board.step(*tile as usize);
+        }
+
+        if score(&board, start.active) - score(&start, start.active) != self.points {
+            return Err(PuzzleError::Points);
+        }
+
+        // the answers are part of the solution, so the search sees them
+        let search = Search {
+            depth: self.solution.len() as u8 - 1,
+        };
+        let evals = search.evaluate(&start);
+        let first = self.solution[0] as usize;
+        let shape = |tile: usize| start.options()[tile].shape(Direction::North);
+
+        // tiles of the same shape are the same answer
+        let ambiguous = evals.iter().any(|(tile, eval)| {
+            *tile != first && *eval >= evals[first].1 && shape(*tile) != shape(first)
+        });
+
+        if ambiguous {
+            return Err(PuzzleError::Ambiguous);
+        }
+
+        Ok(())
+    }
+
+    /// One line of a puzzle file.
+    pub fn encode(&self) -> String {
+        let solution: String = self
+            .solution
+            .iter()
+            .map(|tile| std::char::from_digit(*tile as u32, 36).unwrap())
+            .collect();
+
+        format!("{} {} {}", self.record.encode(), solution, self.points)
+    }
+
+    /// Read a puzzle from [`Puzzle::encode`], checking that it can be solved.
+    pub fn decode(line: &str) -> Result<Self, PuzzleError> {
+        let mut fields = line.split_whitespace();
+
+        let record =
+            Record::decode(fields.next().unwrap_or_default()).map_err(PuzzleError::Code)?;
+        let solution = fields
+            .next()
+            .ok_or(PuzzleError::Missing)?
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                c.to_digit(36)
+                    .map(|tile| tile as u8)
+                    .ok_or(PuzzleError::Tile(i))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let points = fields
+            .next()
+            .and_then(|points| points.parse().ok())
+            .ok_or(PuzzleError::Points)?;
+
+        let puzzle = Self {
+            record,
+            solution,
+            points,
+        };
+
+        puzzle.validate()?;
+        Ok(puzzle)
+    }
+
+    /// Read every puzzle of a file, or the first error with its line number, counted from one.
+    pub fn decode_all(text: &str) -> Result<Vec<Self>, (usize, PuzzleError)> {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .map(|(i, line)| Self::decode(line).map_err(|err| (i + 1, err)))
+            .collect()
+    }
+
+    /// Find puzzles in games of a bot searching `depth` steps against itself,
+    /// one game after every opening of `opening` steps from `start`.
+    /// A position is a puzzle if one tile captures at least `margin` points
+    /// more than any other, and [`Puzzle::validate`] accepts it.
+    // the games of the bot alone would all be the same
+    pub fn mine(start: &Record, opening: u8, depth: u8, margin: u8) -> Vec<Self> {
+        let mut puzzles = Vec::new();
+        let mut openings = vec![start.clone()];
+
+        for _ in 0..opening {
+            openings = openings
+                .into_iter()
+                .flat_map(|record| {
+                    let options = record.replay().map_or(0, |board| board.options().len());
+
+                    (0..options).map(move |tile| {
+                        let mut next = record.clone();
+                        next.moves.push(Action::Tile(tile as u8));
+                        next
+                    })
+                })
+                .collect();
+        }
+
+        let search = Search { depth };
+
+        for mut record in openings {
+            // the openings only set tiles of the options
+            let mut board = match record.replay() {
+                Ok(board) => board,
+                Err(_) => continue,
+            };
+
+            while let Some((tile, _)) = search.best(&board) {
+                // the points of a step alone don't see the answers of the search
+                let puzzle = Self::capture(&record, &board, margin)
+                    .filter(|puzzle| puzzle.validate().is_ok());
+
+                if let Some(puzzle) = puzzle {
+                    // openings often lead to the same positions
+                    if !puzzles
+                        .iter()
+                        .any(|other: &Self| other.record.moves == puzzle.record.moves)
+                    {
+                        puzzles.push(puzzle);
+                    }
+                }
+
+                board.step(tile);
+                record.moves.push(Action::Tile(tile as u8));
+            }
+        }
+
+        puzzles
+    }
+
+    /// The puzzle of `board` after `record`, if one tile captures at least `margin`
+    /// points more than any other, without leaving the board.
+    fn capture(record: &Record, board: &Board, margin: u8) -> Option<Self> {
+        let captured = |tile: usize| {
+            let mut next = board.clone();
+            next.step(tile);
+
+            let lost = matches!(next.state, State::Victory(winner) if winner != board.active);
+
+            if lost {
+                0
+            } else {
+                score(&next, board.active) - score(board, board.active)
+            }
+        };
+
+        let mut captures: Vec<(usize, u64)> = (0..board.options().len())
+            .map(|tile| (tile, captured(tile)))
+            .collect();
+        captures.sort_by_key(|(_, points)| std::cmp::Reverse(*points));
+
+        match captures.as_slice() {
+            [(tile, best), (_, second), ..] if *best >= *second + margin as u64 => Some(Self {
+                record: record.clone(),
+                solution: vec![*tile as u8],
+                points: *best,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PuzzleError::Code(err) => write!(f, "{}", err),
+            PuzzleError::Missing => write!(f, "missing solution"),
+            PuzzleError::Tile(i) => write!(f, "invalid tile at {} of the solution", i),
+            PuzzleError::Over => write!(f, "the game ends before the solution"),
+            PuzzleError::Answer => write!(f, "the solution ends with an answer"),
+            PuzzleError::Ambiguous => write!(f, "another tile is as good as the solution"),
+            PuzzleError::Points => write!(f, "the points are not the ones of the solution"),
+        }
+    }
+}
+
+impl std::error::Error for PuzzleError {}
+
+fn score(board: &Board, player: Player) -> u64 {
+    match player {
+        Player::Gamma => board.score.gamma,
+        Player::Delta => board.score.delta,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINE: &str = "1.11.5.5.n.0.0503760332 1 6";
+
+    #[test]
+    fn line_round_trips() {
+        let puzzle = Puzzle::decode(LINE).unwrap();
+
+        assert_eq!(puzzle.points, 6);
+        assert_eq!(puzzle.encode(), LINE);
+    }
+
+    #[test]
+    fn validate_rejects_wrong_puzzles() {
+        // the position of `LINE`, with another solution and points
+        let err = |rest| Puzzle::decode(&format!("1.11.5.5.n.0.0503760332 {}", rest)).err();
+
+        assert_eq!(err(""), Some(PuzzleError::Missing));
+        assert_eq!(err("1"), Some(PuzzleError::Points));
+        assert_eq!(err("1 5"), Some(PuzzleError::Points));
+        assert_eq!(err("10 6"), Some(PuzzleError::Answer));
+        assert_eq!(err("z 6"), Some(PuzzleError::Tile(0)));
+        // not the best tile
+        assert_eq!(err("0 0"), Some(PuzzleError::Ambiguous));
+    }
+
+    #[test]
+    fn mined_puzzles_validate() {
+        let puzzles = Puzzle::mine(&Record::default(), 1, 0, 1);
+
+        assert!(!puzzles.is_empty());
+        assert!(puzzles.iter().all(|puzzle| puzzle.validate().is_ok()));
+    }
+}
@@ -0,0 +1,316 @@
+use super::{
+    action::{Action, ActionError},
+    board::{Arrow, Board, Player},
+    curve::TileSet,
+};
+use crate::math::prelude::*;
+use std::{fmt, ops::RangeInclusive};
+
+/// Number of points in width and height a [`Record`] can have.
+// boards smaller than the largest tile are no fun,
+// larger ones may move the arrow out of `i8`
+pub const SIZES: RangeInclusive<u8> = 5..=31;
+
+/// A game as its start configuration and every [`Action`] of its players.
+// not the boards of every step, because they are cheap to replay
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Record {
+    // number of points on the board in width and height
+    pub size: u8,
+    pub arrow: Arrow,
+    pub tiles: TileSet,
+    // in the order they were done, tiles as indices, see `Board::parse_tile` for notation
+    pub moves: Vec<Action>,
+}
+
+impl Default for Record {
+    // same config as the default `Board`
+    fn default() -> Self {
+        Self::with_size(11)
+    }
+}
+
+impl Record {
+    /// Record of a board with `size` points in width and height,
+    /// starting in the middle facing north.
+    pub fn with_size(size: u8) -> Self {
+        Self {
+            size,
+            arrow: Arrow {
+                pos: Vec2D {
+                    x: size as i8 / 2,
+                    y: size as i8 / 2,
+                },
+                dir: Direction::North,
+            },
+            tiles: TileSet::default(),
+            moves: Vec::new(),
+        }
+    }
+
+    /// The [`Board`] before the first step.
+    pub fn start(&self) -> Board {
+        Board {
+            arrow: self.arrow.clone(),
+            tiles: self.tiles.tiles(),
+            points: vec![vec![None; self.size as usize]; self.size as usize],
+            ..Default::default()
+        }
+    }
+
+    /// The [`Board`] after the last move.
+    /// Fails on the first move that can't be done.
+    pub fn replay(&self) -> Result<Board, ActionError> {
+        let mut board = self.start();
+
+        for action in self.moves.iter() {
+            board.act(*action)?;
+        }
+
+        Ok(board)
+    }
+
+    /// The [`Board`] before the first and after every move.
+    /// Fails on the first move that can't be done.
+    pub fn boards(&self) -> Result<Vec<Board>, ActionError> {
+        let mut board = self.start();
+        let mut boards = Vec::with_capacity(self.moves.len() + 1);
+
+        for action in self.moves.iter() {
+            boards.push(board.clone());
+            board.act(*action)?;
+        }
+
+        boards.push(board);
+        Ok(boards)
+    }
+
+    /// Test if the board has one of the [`SIZES`] with the arrow on it,
+    /// and every move can be done after the ones before.
+    pub fn is_valid(&self) -> bool {
+        self.has_valid_board() && self.replay().is_ok()
+    }
+
+    fn has_valid_board(&self) -> bool {
+        let on_board = |c: i8| c >= 0 && (c as i16) < self.size as i16;
+
+        SIZES.contains(&self.size) && on_board(self.arrow.pos.x) && on_board(self.arrow.pos.y)
+    }
+}
+
+/// Reasons a link code can't be read, see [`Record::decode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeError {
+    Version,
+    // a missing or malformed field of the header
+    Header,
+    // malformed move at the index of the moves
+    Move(usize),
+    // the moves can't be done, see `Record::is_valid`
+    Invalid,
+}
+
+// # link code
+// fields separated by `.`, only characters allowed in URL fragments:
+// version, size, arrow x, arrow y, arrow direction `n`, `e`, `s` or `w`,
+// index into `TileSet::ALL`, moves
+// moves: tiles as one base 36 digit, other actions as an upper case
+// letter `R`esign, `O`ffer, `A`ccept or `X` decline, then `g` or `d` for the player
+// example: `1.11.5.5.n.0.3a7`
+// tiles are indices, not notation like `R2u`: one character instead of three keeps
+// links short, and the notation of a move follows from `Curve::shape` on replay
+const CODE_VERSION: &str = "1";
+
+impl Record {
+    /// Compact text of the record, to share it in links.
+    /// Panics if a tile index has more than one base 36 digit, no tile set has that many.
+    pub fn encode(&self) -> String {
+        let dir = match self.arrow.dir {
+            Direction::North => 'n',
+            Direction::East => 'e',
+            Direction::South => 's',
+            Direction::West => 'w',
+        };
+        let tiles = TileSet::ALL
+            .iter()
+            .position(|tiles| *tiles == self.tiles)
+            .unwrap_or_default();
+
+        let mut moves = String::with_capacity(self.moves.len());
+        for action in self.moves.iter() {
+            let (letter, player) = match action {
+                Action::Tile(tile) => {
+                    moves.push(std::char::from_digit(*tile as u32, 36).unwrap());
+                    continue;
+                }
+                Action::Resign(player) => ('R', player),
+                Action::OfferDraw(player) => ('O', player),
+                Action::AcceptDraw(player) => ('A', player),
+                Action::DeclineDraw(player) => ('X', player),
+            };
+
+            moves.push(letter);
+            moves.push(match player {
+                Player::Gamma => 'g',
+                Player::Delta => 'd',
+            });
+        }
+
+        format!(
+            "{}.{}.{}.{}.{}.{}.{}",
+            CODE_VERSION, self.size, self.arrow.pos.x, self.arrow.pos.y, dir, tiles, moves
+        )
+    }
+
+    /// Read a record from [`Record::encode`], checking that it is valid.
+    pub fn decode(code: &str) -> Result<Self, CodeError> {
+        let fields: Vec<&str> = code.trim().split('.').collect();
+
+        let (size, x, y, dir, tiles, moves) = match fields.as_slice() {
+            [CODE_VERSION, size, x, y, dir, tiles, moves] => (size, x, y, dir, tiles, moves),
+            [version, ..] if *version != CODE_VERSION => return Err(CodeError::Version),
+            _ => return Err(CodeError::Header),
+        };
+
+        fn number<T: std::str::FromStr>(field: &str) -> Result<T, CodeError> {
+            field.parse().map_err(|_| CodeError::Header)
+        }
+
+        let size: u8 = number(size)?;
+        let mut record = Record {
+            tiles: *TileSet::ALL
+                .get(number::<usize>(tiles)?)
+                .ok_or(CodeError::Header)?,
+            ..Record::with_size(size)
+        };
+        record.arrow = Arrow {
+            pos: Vec2D {
+                x: number(x)?,
+                y: number(y)?,
+            },
+            dir: match *dir {
+                "n" => Direction::North,
+                "e" => Direction::East,
+                "s" => Direction::South,
+                "w" => Direction::West,
+                _ => return Err(CodeError::Header),
+            },
+        };
+
+        // before the moves, which would panic on larger boards
+        if !record.has_valid_board() {
+            return Err(CodeError::Header);
+        }
+
+        let mut chars = moves.chars().enumerate();
+        while let Some((i, c)) = chars.next() {
+            let action = match c {
+                'R' | 'O' | 'A' | 'X' => {
+                    let player = match chars.next() {
+                        Some((_, 'g')) => Player::Gamma,
+                        Some((_, 'd')) => Player::Delta,
+                        _ => return Err(CodeError::Move(i)),
+                    };
+
+                    match c {
+                        'R' => Action::Resign(player),
+                        'O' => Action::OfferDraw(player),
+                        'A' => Action::AcceptDraw(player),
+                        _ => Action::DeclineDraw(player),
+                    }
+                }
+                c => Action::Tile(c.to_digit(36).ok_or(CodeError::Move(i))? as u8),
+            };
+
+            record.moves.push(action);
+        }
+
+        match record.replay() {
+            Ok(_) => Ok(record),
+            Err(_) => Err(CodeError::Invalid),
+        }
+    }
+}
+
+impl fmt::Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodeError::Version => write!(f, "unknown code version"),
+            CodeError::Header => write!(f, "malformed board in code"),
+            CodeError::Move(i) => write!(f, "malformed move at {}", i),
+            CodeError::Invalid => write!(f, "the moves can't be played"),
+        }
+    }
+}
+
+impl std::error::Error for CodeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game() -> Record {
+        Record {
+            arrow: Arrow {
+                pos: Vec2D { x: 3, y: 4 },
+                dir: Direction::East,
+            },
+            tiles: TileSet::ALL[TileSet::ALL.len() - 1],
+            moves: vec![
+                Action::Tile(0),
+                Action::Tile(3),
+                Action::OfferDraw(Player::Gamma),
+                Action::DeclineDraw(Player::Delta),
+                Action::Tile(1),
+                Action::Resign(Player::Delta),
+            ],
+            ..Record::with_size(9)
+        }
+    }
+
+    #[test]
+    fn code_round_trips() {
+        let record = game();
+        assert!(record.is_valid());
+
+        let code = record.encode();
+        let decoded = Record::decode(&code).unwrap();
+
+        assert_eq!(decoded.encode(), code);
+        assert_eq!(decoded.size, 9);
+        assert!(decoded.tiles == record.tiles);
+        assert!(decoded.moves == record.moves);
+    }
+
+    #[test]
+    fn decode_rejects_bad_codes() {
+        let err = |code| Record::decode(code).err();
+
+        assert_eq!(err("2.11.5.5.n.0."), Some(CodeError::Version));
+        assert_eq!(err("1.11.5.5.n.0"), Some(CodeError::Header));
+        assert_eq!(err("1.11.5.5.x.0."), Some(CodeError::Header));
+        assert_eq!(err("1.11.5.5.n.99."), Some(CodeError::Header));
+        assert_eq!(err("1.11.11.5.n.0."), Some(CodeError::Header));
+        assert_eq!(err("1.11.-1.5.n.0."), Some(CodeError::Header));
+        assert_eq!(err("1.4.2.2.n.0."), Some(CodeError::Header));
+        // would move the arrow out of `i8`
+        assert_eq!(err("1.127.126.126.e.0.0"), Some(CodeError::Header));
+        assert_eq!(err("1.11.5.5.n.0.3!"), Some(CodeError::Move(1)));
+        assert_eq!(err("1.11.5.5.n.0.3R"), Some(CodeError::Move(1)));
+        assert_eq!(err("1.11.5.5.n.0.z"), Some(CodeError::Invalid));
+        assert_eq!(err("1.11.5.5.n.0.RgRd"), Some(CodeError::Invalid));
+    }
+
+    #[test]
+    fn replay_fails_on_invalid_moves() {
+        let record = Record {
+            moves: vec![Action::Tile(0), Action::AcceptDraw(Player::Gamma)],
+            ..Record::default()
+        };
+
+        assert_eq!(record.replay().err(), Some(ActionError::NoOffer));
+        assert_eq!(record.boards().err(), Some(ActionError::NoOffer));
+        assert!(!record.is_valid());
+    }
+}
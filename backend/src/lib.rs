@@ -9,15 +9,32 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT; */
 // the precision type
 type Float = f32;
 
+mod bot;
 mod game;
 mod math;
+#[cfg(feature = "serde")]
+mod protocol;
+mod render;
 // re-export for frontend use
 pub mod prelude {
     pub use crate::{
+        bot::{
+            book::{Book, BookError},
+            search::{evaluate, Eval, Search, WIN},
+            Agent, Bot,
+        },
         game::{
-            board::{Arrow, Board, Player},
-            curve::Curve,
+            action::{Action, ActionError},
+            board::{Arrow, Board, Capture, Player, Score, State},
+            clock::{Clock, Increment, Millis, TimeControl},
+            curve::{Curve, NotationError, Shape, TileSet},
+            puzzle::{Puzzle, PuzzleError},
+            record::{CodeError, Record, SIZES},
         },
         math::prelude::*,
+        render::svg::SvgStyle,
     };
+
+    #[cfg(feature = "serde")]
+    pub use crate::protocol::{ClientMsg, GameInfo, ServerMsg, TileChoice};
 }
@@ -3,6 +3,7 @@
 
 /// `u8` version of an angle, cardinal directions.
 #[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     North,
     South,
@@ -2,6 +2,7 @@ use crate::Float;
 
 /// 2-Dimensional vector of `T` on `x` and `y`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vec2D<T> {
     pub x: T,
     pub y: T,
@@ -0,0 +1,134 @@
+//! Messages of the WebSocket protocol between the game server and its clients.
+//! Every message is one JSON text frame, tagged by its `type`.
+//! See `server/PROTOCOL.md` for the message flow.
+
+use crate::game::{
+    action::Action,
+    board::{Board, Player},
+    clock::{Millis, TimeControl},
+    curve::{NotationError, TileSet},
+    record::Record,
+};
+use serde::{Deserialize, Serialize};
+
+/// Messages from a client to the server.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMsg {
+    /// List the games of the server.
+    Lobby,
+    /// Host a new game on a board of `size` and take the first seat.
+    Create {
+        name: String,
+        size: u8,
+        #[serde(default)]
+        tiles: TileSet,
+        // `None` for untimed games
+        #[serde(default)]
+        clock: Option<TimeControl>,
+    },
+    /// Take the free seat of a game, or the abandoned seat of `token`.
+    Join {
+        game: u32,
+        name: String,
+        // sent by [`ServerMsg::Seated`], only to reclaim a seat
+        #[serde(default)]
+        token: Option<String>,
+    },
+    /// Watch a game without a seat.
+    Spectate { game: u32 },
+    /// Choose a tile of [`Board::options()`], only on your turn.
+    Tile { tile: TileChoice },
+    /// Give up the game.
+    Resign,
+    /// Propose a draw to the opponent.
+    OfferDraw,
+    /// Agree to the draw offered by the opponent.
+    AcceptDraw,
+    /// Refuse the draw offered by the opponent.
+    DeclineDraw,
+}
+
+/// A tile of [`Board::options()`], by its index or in notation like `R2u`.
+// untagged, so `"tile":3` and `"tile":"R2u"` both work
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TileChoice {
+    Index(u8),
+    Notation(String),
+}
+
+impl TileChoice {
+    /// The [`Action::Tile`] of the choice on `board`.
+    /// An index is checked once the action is done.
+    pub fn action(&self, board: &Board) -> Result<Action, NotationError> {
+        match self {
+            TileChoice::Index(tile) => Ok(Action::Tile(*tile)),
+            TileChoice::Notation(notation) => board.parse_tile(notation),
+        }
+    }
+}
+
+/// Messages from the server to a client.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMsg {
+    /// The games of the server.
+    Lobby { games: Vec<GameInfo> },
+    /// You took the seat of `player` in `game`, `token` takes it back once abandoned.
+    /// The `record` holds every step played so far, for returning players.
+    Seated {
+        game: u32,
+        player: Player,
+        token: String,
+        record: Record,
+    },
+    /// You watch `game`, with every step played so far.
+    Spectating { game: u32, record: Record },
+    /// Both seats are taken, the game begins.
+    Start { gamma: String, delta: String },
+    /// The full [`Board`], sent on start and after every step.
+    Update { board: Board },
+    /// Time left of both players, sent after every update of a timed game.
+    // not the `Clock` itself, because its times are relative to the server
+    Clock {
+        control: TimeControl,
+        gamma: Millis,
+        delta: Millis,
+        // whose time runs since the message was sent
+        running: Option<Player>,
+    },
+    /// An action besides a tile, sent before its update.
+    Action { action: Action },
+    /// Outcome of a step, sent before its update.
+    Step {
+        step: u8,
+        player: Player,
+        tile: u8,
+        // points collected by the step
+        captured: u64,
+    },
+    /// The seat of `player` was abandoned, it is lost after `seconds`.
+    Abandoned { player: Player, seconds: u64 },
+    /// The abandoned seat of `player` was taken again.
+    Returned { player: Player },
+    /// A message could not be handled, nothing changed.
+    Error { message: String },
+}
+
+/// Summary of a game in the [`ServerMsg::Lobby`].
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GameInfo {
+    pub game: u32,
+    pub size: u8,
+    pub tiles: TileSet,
+    pub clock: Option<TimeControl>,
+    // names of the seated players, `None` for free seats
+    pub gamma: Option<String>,
+    pub delta: Option<String>,
+    pub spectators: u32,
+    // step of the board, `1` before the start
+    pub step: u8,
+    // a seat can be joined
+    pub open: bool,
+}
@@ -0,0 +1,2 @@
+pub(crate) mod replay;
+pub(crate) mod svg;
@@ -0,0 +1,191 @@
+use super::svg::{open_svg, SvgStyle};
+use crate::{
+    game::{action::ActionError, board::Player, record::Record},
+    Float,
+};
+use std::fmt::Write;
+
+impl Record {
+    /// Render the game to an animated SVG using [SMIL](https://developer.mozilla.org/en-US/docs/Web/SVG/SVG_animation_with_SMIL).
+    /// Every step takes `seconds` to draw its curve, captured points fade in at the end of their step.
+    /// Fails on the first move that can't be done.
+    // `write!` into a `String` can't fail, so the results are ignored
+    pub fn animated_svg(&self, style: &SvgStyle, seconds: Float) -> Result<String, ActionError> {
+        let mut boards = self.boards()?;
+        // one board per curve, actions besides tiles don't draw anything
+        boards.dedup_by_key(|board| board.step);
+        let last = boards.last().unwrap();
+
+        let mut svg = open_svg(style, self.size as usize);
+
+        // # path
+        // every curve is a polyline, so its length is known for the dash animation
+        let _ = write!(
+            svg,
+            r#"<g fill="none" stroke="{}" stroke-width="{}" stroke-linecap="round">"#,
+            style.curve, style.curve_width
+        );
+
+        for (i, curve) in last.path.iter().enumerate() {
+            let mut vertices = curve.path(0.0, 1.0);
+            // `Curve::path` excludes the end point
+            vertices.push(curve.end.into());
+
+            let length: Float = vertices
+                .windows(2)
+                .map(|w| ((w[1].x - w[0].x).powi(2) + (w[1].y - w[0].y).powi(2)).sqrt())
+                .sum();
+
+            let _ = write!(
+                svg,
+                r#"<polyline stroke-dasharray="{l}" stroke-dashoffset="{l}" points=""#,
+                l = length
+            );
+
+            for vertex in vertices {
+                let _ = write!(svg, "{},{} ", vertex.x, vertex.y);
+            }
+
+            let _ = write!(
+                svg,
+                r#""><animate attributeName="stroke-dashoffset" from="{}" to="0" begin="{}s" dur="{}s" fill="freeze"/></polyline>"#,
+                length,
+                i as Float * seconds,
+                seconds
+            );
+        }
+
+        svg.push_str("</g>");
+
+        // # points
+        for (j, row) in last.points.iter().enumerate() {
+            for (i, point) in row.iter().enumerate() {
+                let _ = write!(
+                    svg,
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                    i, j, style.point_radius, style.point
+                );
+
+                if let Some(player) = point {
+                    // the first board, which has the point captured
+                    let step = boards
+                        .iter()
+                        .position(|board| board.points[j][i].is_some())
+                        .unwrap();
+
+                    let _ = write!(
+                        svg,
+                        r#"<circle cx="{}" cy="{}" r="{}" fill="{}" opacity="0"><animate attributeName="opacity" from="0" to="1" begin="{}s" dur="{}s" fill="freeze"/></circle>"#,
+                        i,
+                        j,
+                        style.point_radius,
+                        match player {
+                            Player::Gamma => &style.gamma,
+                            Player::Delta => &style.delta,
+                        },
+                        step as Float * seconds,
+                        seconds / 2.0
+                    );
+                }
+            }
+        }
+
+        // # arrow
+        // one arrow per board, only visible during its step
+        for (i, board) in boards.iter().enumerate() {
+            let pos = board.arrow.pos;
+            let x = pos.x as Float + board.arrow.dir.sin() as Float / 2.0;
+            let y = pos.y as Float - board.arrow.dir.cos() as Float / 2.0;
+
+            let _ = write!(
+                svg,
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}" visibility="hidden"><set attributeName="visibility" to="visible" begin="{}s""#,
+                pos.x,
+                pos.y,
+                x,
+                y,
+                style.arrow,
+                style.arrow_width,
+                i as Float * seconds
+            );
+
+            // the last arrow stays
+            if i + 1 < boards.len() {
+                let _ = write!(svg, r#" dur="{}s""#, seconds);
+            } else {
+                svg.push_str(r#" fill="freeze""#);
+            }
+
+            svg.push_str("/></line>");
+        }
+
+        svg.push_str("</svg>");
+        Ok(svg)
+    }
+
+    /// Render the game to one static SVG per board, before the first and after every move.
+    /// Fails on the first move that can't be done.
+    pub fn frames(&self, style: &SvgStyle) -> Result<Vec<String>, ActionError> {
+        Ok(self
+            .boards()?
+            .iter()
+            .map(|board| board.svg(style))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::action::Action;
+
+    fn record(moves: Vec<Action>) -> Record {
+        Record {
+            moves,
+            ..Record::default()
+        }
+    }
+
+    #[test]
+    fn actions_besides_tiles_get_frames_but_no_curves() {
+        let record = record(vec![
+            Action::Tile(0),
+            Action::OfferDraw(Player::Delta),
+            Action::DeclineDraw(Player::Gamma),
+            Action::Tile(0),
+        ]);
+        let style = SvgStyle::default();
+
+        assert_eq!(record.frames(&style).unwrap().len(), 5);
+
+        let svg = record.animated_svg(&style, 1.0).unwrap();
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        assert_eq!(svg.matches("<line").count(), 3);
+    }
+
+    #[test]
+    fn captured_points_fade_in_at_their_step() {
+        let record = record(vec![Action::Tile(0), Action::Tile(0), Action::Tile(5)]);
+        let svg = record.animated_svg(&SvgStyle::default(), 2.0).unwrap();
+
+        assert_eq!(svg.matches(r#"attributeName="opacity""#).count(), 1);
+        assert!(
+            svg.contains(r#"<animate attributeName="opacity" from="0" to="1" begin="6s" dur="1s""#)
+        );
+    }
+
+    #[test]
+    fn invalid_moves_fail() {
+        let record = record(vec![Action::Tile(0), Action::Tile(200)]);
+        let style = SvgStyle::default();
+
+        assert!(matches!(
+            record.animated_svg(&style, 1.0),
+            Err(ActionError::NoTile(200))
+        ));
+        assert!(matches!(
+            record.frames(&style),
+            Err(ActionError::NoTile(200))
+        ));
+    }
+}
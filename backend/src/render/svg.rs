@@ -0,0 +1,282 @@
+use crate::{
+    game::board::{Board, Player},
+    Float,
+};
+use std::fmt::Write;
+
+/// Colours and sizes of a standalone SVG of a [`Board`].
+/// Colours are any SVG paint, like `"black"` or `"#00f"`.
+// not the classes of the frontend, because the SVG has to
+// look the same without any stylesheet
+#[derive(Clone)]
+pub struct SvgStyle {
+    // `None` for a transparent background
+    pub background: Option<String>,
+    pub point: String,
+    pub gamma: String,
+    pub delta: String,
+    pub curve: String,
+    pub arrow: String,
+    // fill of the highlighted capture, drawn translucent
+    pub capture: String,
+    pub curve_width: Float,
+    pub arrow_width: Float,
+    pub point_radius: Float,
+    // pixels per board unit of the `width` and `height`
+    pub scale: Float,
+    // label every curve with the step it was placed at
+    pub move_numbers: bool,
+    // fill the regions of the latest capture
+    pub highlight_capture: bool,
+}
+
+impl Default for SvgStyle {
+    // same look as `default.css` of the frontend
+    fn default() -> Self {
+        Self {
+            background: None,
+            point: "black".to_string(),
+            gamma: "blue".to_string(),
+            delta: "green".to_string(),
+            curve: "black".to_string(),
+            arrow: "red".to_string(),
+            capture: "orange".to_string(),
+            curve_width: 0.1,
+            arrow_width: 0.1,
+            point_radius: 0.1,
+            scale: 40.0,
+            move_numbers: false,
+            highlight_capture: false,
+        }
+    }
+}
+
+/// Start an SVG document of a board of `points` in width and height,
+/// with the background of the `style`. The caller closes the `<svg>`.
+// shared by the still and the animated SVG, so both frame the board alike
+pub(crate) fn open_svg(style: &SvgStyle, points: usize) -> String {
+    let mut svg = String::new();
+    // one unit of margin around the points, same as the frontend
+    let size = points as Float + 1.0;
+
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{w}" viewBox="-1 -1 {s} {s}">"#,
+        w = size * style.scale,
+        s = size,
+    );
+
+    if let Some(background) = &style.background {
+        let _ = write!(
+            svg,
+            r#"<rect x="-1" y="-1" width="{s}" height="{s}" fill="{}"/>"#,
+            background,
+            s = size,
+        );
+    }
+
+    svg
+}
+
+impl Board {
+    /// Render the [`Board`] to a standalone SVG document.
+    // `write!` into a `String` can't fail, so the results are ignored
+    pub fn svg(&self, style: &SvgStyle) -> String {
+        let mut svg = open_svg(style, self.points.len());
+
+        // reversed stack draw, the capture lies under the path
+        if style.highlight_capture {
+            self.capture_svg(&mut svg, style);
+        }
+
+        self.path_svg(&mut svg, style);
+        self.points_svg(&mut svg, style);
+        self.arrow_svg(&mut svg, style);
+
+        svg.push_str("</svg>");
+        svg
+    }
+
+    fn capture_svg(&self, svg: &mut String, style: &SvgStyle) {
+        // the regions of the latest step which collected points
+        let step = match self.captures.last() {
+            Some(capture) => capture.step,
+            None => return,
+        };
+
+        for capture in self.captures.iter().filter(|capture| capture.step == step) {
+            let _ = write!(
+                svg,
+                r#"<polygon fill="{}" fill-opacity="0.3" points=""#,
+                style.capture
+            );
+
+            for vertex in capture.poly.iter() {
+                let _ = write!(svg, "{},{} ", vertex.x, vertex.y);
+            }
+
+            svg.push_str(r#""/>"#);
+        }
+    }
+
+    fn path_svg(&self, svg: &mut String, style: &SvgStyle) {
+        let _ = write!(
+            svg,
+            r#"<g fill="none" stroke="{}" stroke-width="{}">"#,
+            style.curve, style.curve_width
+        );
+
+        for curve in self.path.iter() {
+            let _ = write!(
+                svg,
+                r#"<path d="M {} {} Q {} {} {} {}"/>"#,
+                curve.start.x, curve.start.y, curve.mid.x, curve.mid.y, curve.end.x, curve.end.y
+            );
+        }
+
+        svg.push_str("</g>");
+
+        if style.move_numbers {
+            let _ = write!(
+                svg,
+                r#"<g fill="{}" font-family="sans-serif" font-size="0.4" text-anchor="middle" dominant-baseline="central">"#,
+                style.curve
+            );
+
+            for (i, curve) in self.path.iter().enumerate() {
+                // the middle of the curve, not the control point
+                let label = curve.point(0.5);
+                // the first step is not indexed at zero, same as `Board.step`
+                let _ = write!(
+                    svg,
+                    r#"<text x="{}" y="{}">{}</text>"#,
+                    label.x,
+                    label.y,
+                    i + 1
+                );
+            }
+
+            svg.push_str("</g>");
+        }
+    }
+
+    fn points_svg(&self, svg: &mut String, style: &SvgStyle) {
+        for (j, row) in self.points.iter().enumerate() {
+            for (i, point) in row.iter().enumerate() {
+                let fill = match point {
+                    Some(Player::Gamma) => &style.gamma,
+                    Some(Player::Delta) => &style.delta,
+                    None => &style.point,
+                };
+
+                let _ = write!(
+                    svg,
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                    i, j, style.point_radius, fill
+                );
+            }
+        }
+    }
+
+    fn arrow_svg(&self, svg: &mut String, style: &SvgStyle) {
+        let pos = self.arrow.pos;
+        // the arrow points half a unit into its direction, `y` is flipped in screen space
+        let x = pos.x as Float + self.arrow.dir.sin() as Float / 2.0;
+        let y = pos.y as Float - self.arrow.dir.cos() as Float / 2.0;
+
+        let _ = write!(
+            svg,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}"/>"#,
+            pos.x, pos.y, x, y, style.arrow, style.arrow_width
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::record::Record;
+
+    // captures at step 3, then two regions at step 5
+    fn captured() -> Board {
+        let mut board = Board::default();
+
+        for tile in [0, 0, 5, 0, 3].iter() {
+            board.step(*tile);
+        }
+
+        board
+    }
+
+    #[test]
+    fn header_frames_the_board() {
+        let style = SvgStyle {
+            scale: 10.0,
+            ..SvgStyle::default()
+        };
+        let svg = Record::with_size(5).start().svg(&style);
+
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="60" height="60" viewBox="-1 -1 6 6">"#
+        ));
+        assert!(svg.ends_with("</svg>"));
+    }
+
+    #[test]
+    fn highlight_capture_fills_the_latest_step() {
+        let board = captured();
+        let style = SvgStyle {
+            highlight_capture: true,
+            ..SvgStyle::default()
+        };
+
+        assert_eq!(board.captures.len(), 3);
+        assert_eq!(board.svg(&style).matches("<polygon").count(), 2);
+        assert_eq!(
+            board.svg(&SvgStyle::default()).matches("<polygon").count(),
+            0
+        );
+    }
+
+    #[test]
+    fn move_numbers_label_every_curve() {
+        let board = captured();
+        let style = SvgStyle {
+            move_numbers: true,
+            ..SvgStyle::default()
+        };
+        let svg = board.svg(&style);
+
+        assert_eq!(svg.matches("<text").count(), board.path.len());
+        assert!(svg.contains(">1</text>"));
+        assert!(svg.contains(&format!(">{}</text>", board.path.len())));
+        assert!(!board.svg(&SvgStyle::default()).contains("<text"));
+    }
+
+    #[test]
+    fn style_colours_are_used() {
+        let style = SvgStyle {
+            background: Some("#000001".to_string()),
+            point: "#000002".to_string(),
+            gamma: "#000003".to_string(),
+            delta: "#000004".to_string(),
+            curve: "#000005".to_string(),
+            arrow: "#000006".to_string(),
+            capture: "#000007".to_string(),
+            highlight_capture: true,
+            ..SvgStyle::default()
+        };
+        let mut board = captured();
+        // the captures above are all of Gamma
+        board.points[0][0] = Some(Player::Delta);
+        let svg = board.svg(&style);
+
+        assert!(svg.contains(r##"<rect x="-1" y="-1" width="12" height="12" fill="#000001"/>"##));
+        assert!(svg.contains(r##"fill="#000002""##));
+        assert!(svg.contains(r##"fill="#000003""##));
+        assert!(svg.contains(r##"fill="#000004""##));
+        assert!(svg.contains(r##"stroke="#000005""##));
+        assert!(svg.contains(r##"stroke="#000006""##));
+        assert!(svg.contains(r##"<polygon fill="#000007""##));
+    }
+}
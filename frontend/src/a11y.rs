@@ -0,0 +1,132 @@
+//! Text alternatives of the board, for screen readers.
+
+use super::{
+    i18n::{fill, Texts},
+    online::player_name,
+    Polycentrics,
+};
+use backend::prelude as pyc;
+
+/// Spoken name of a tile of the pad, like "Turn right, radius 2".
+// tiles of the set start at the origin facing north
+pub(crate) fn tile_label(texts: &Texts, curve: &pyc::Curve) -> String {
+    shape_label(texts, curve.shape(pyc::Direction::North))
+}
+
+/// Spoken name of a tile set on the board, seen from the `arrow` before it.
+fn placed_label(texts: &Texts, arrow: &pyc::Arrow, curve: &pyc::Curve) -> String {
+    shape_label(texts, curve.shape(arrow.dir))
+}
+
+fn shape_label(texts: &Texts, shape: pyc::Shape) -> String {
+    fill(
+        if shape.forward {
+            texts.tile
+        } else {
+            texts.tile_backward
+        },
+        &[
+            &if shape.right { texts.right } else { texts.left },
+            &shape.radius,
+        ],
+    )
+}
+
+/// Spoken name of a direction on the board.
+pub(crate) fn direction_name(texts: &Texts, dir: pyc::Direction) -> &'static str {
+    match dir {
+        pyc::Direction::North => texts.north,
+        pyc::Direction::East => texts.east,
+        pyc::Direction::South => texts.south,
+        pyc::Direction::West => texts.west,
+    }
+}
+
+/// Spoken owner of a point of the board.
+pub(crate) fn point_label(texts: &Texts, x: usize, y: usize, owner: Option<pyc::Player>) -> String {
+    fill(
+        texts.point_label,
+        &[
+            &x,
+            &y,
+            &owner.map_or(texts.empty, |player| player_name(texts, player)),
+        ],
+    )
+}
+
+/// Spoken result of the game, `None` while it is pending.
+pub(crate) fn result_label(texts: &Texts, state: &pyc::State) -> Option<String> {
+    match state {
+        pyc::State::Pending => None,
+        pyc::State::Victory(player) => Some(fill(texts.won, &[&player_name(texts, *player)])),
+        pyc::State::Draw => Some(texts.drawn.to_string()),
+    }
+}
+
+impl Polycentrics {
+    /// Spoken summary of the board, for its SVG.
+    pub(crate) fn board_label(&self) -> String {
+        let board = self.shown();
+        let texts = self.texts();
+
+        fill(
+            texts.board_label,
+            &[
+                &board.points.len(),
+                &board.arrow.pos.x,
+                &board.arrow.pos.y,
+                &direction_name(texts, board.arrow.dir),
+            ],
+        )
+    }
+
+    /// Tell screen readers what changed since the board was `before`:
+    /// the tile set, the captured points and the score, offers and the result.
+    pub(crate) fn announce(&mut self, before: &pyc::Board) {
+        let texts = self.texts();
+        let mut text = Vec::new();
+
+        if self.board.step > before.step {
+            let player = before.active;
+
+            if let Some(curve) = self.board.path.last() {
+                text.push(format!(
+                    "{}: {}.",
+                    player_name(texts, player),
+                    placed_label(texts, &before.arrow, curve)
+                ));
+            }
+
+            let captured = match player {
+                pyc::Player::Gamma => self.board.score.gamma - before.score.gamma,
+                pyc::Player::Delta => self.board.score.delta - before.score.delta,
+            };
+            if captured > 0 {
+                text.push(fill(texts.captured, &[&captured]));
+            }
+
+            text.push(fill(
+                texts.score,
+                &[&self.board.score.gamma, &self.board.score.delta],
+            ));
+        }
+
+        match (before.draw_offer, self.board.draw_offer) {
+            (None, Some(player)) => text.push(format!(
+                "{}.",
+                fill(texts.offers_draw, &[&player_name(texts, player)])
+            )),
+            // a step also declines the offer
+            (Some(_), None) if matches!(self.board.state, pyc::State::Pending) => {
+                text.push(texts.offer_declined.to_string())
+            }
+            _ => {}
+        }
+
+        if matches!(before.state, pyc::State::Pending) {
+            text.extend(result_label(texts, &self.board.state));
+        }
+
+        self.announcement = text.join(" ");
+    }
+}
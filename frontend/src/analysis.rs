@@ -0,0 +1,246 @@
+//! Evaluations of the search next to the board, to study positions and games.
+
+use super::{
+    a11y,
+    i18n::{fill, Texts},
+    online::player_name,
+    GameMsg, Mode, Polycentrics,
+};
+use backend::prelude as pyc;
+use yew::prelude::*;
+
+/// What the search thinks of the shown position and of the game so far.
+#[derive(Default)]
+pub(crate) struct Analysis {
+    // moves to the analysed position, `None` before the first analysis
+    position: Option<Vec<pyc::Action>>,
+    // the best tiles of the options first, from the view of the active player
+    lines: Vec<(usize, pyc::Eval)>,
+    // curves of the principal variation
+    line: Vec<pyc::Curve>,
+    // moves of the graphed game, `None` before the first analysis
+    game: Option<Vec<pyc::Action>>,
+    // evaluation before the first and after every move, from the view of gamma
+    graph: Vec<pyc::Eval>,
+}
+
+// steps the search looks ahead, it runs on the main thread,
+// because the worker is busy with hints and the opponent
+const ANALYSIS_DEPTH: u8 = 2;
+
+// shallower, as every position of the game is searched
+const GRAPH_DEPTH: u8 = 0;
+
+// number of best tiles shown
+const LINES: usize = 3;
+
+// score difference at the edge of the graph, decided games are drawn there
+const GRAPH_RANGE: pyc::Eval = 10;
+
+impl Analysis {
+    /// Evaluate the options of `board` and search its principal variation.
+    fn search(&mut self, board: &pyc::Board) {
+        let search = pyc::Search {
+            depth: ANALYSIS_DEPTH,
+        };
+
+        let mut lines = search.evaluate(board);
+        // stable, so equal tiles stay in the order of the options
+        lines.sort_by_key(|(_, eval)| std::cmp::Reverse(*eval));
+        lines.truncate(LINES);
+        self.lines = lines;
+
+        let mut next = board.clone();
+        self.line = search
+            .line(board)
+            .into_iter()
+            .filter_map(|tile| {
+                next.step(tile);
+                next.path.last().cloned()
+            })
+            .collect();
+    }
+
+    /// Evaluate the position before the first and after every move of `record`.
+    fn graph(&mut self, record: &pyc::Record) {
+        let search = pyc::Search { depth: GRAPH_DEPTH };
+
+        self.graph = record
+            .boards()
+            .unwrap_or_default()
+            .iter()
+            .map(|board| {
+                // a decided game has no options to search
+                let eval = search
+                    .best(board)
+                    .map_or_else(|| pyc::evaluate(board), |(_, eval)| eval);
+
+                match board.active {
+                    pyc::Player::Gamma => eval,
+                    pyc::Player::Delta => -eval,
+                }
+            })
+            .collect();
+    }
+}
+
+/// An [`pyc::Eval`] as text, like "+2" or "wins".
+fn eval_label(texts: &Texts, eval: pyc::Eval) -> String {
+    // score differences stay far below the bonus of the winner
+    if eval >= pyc::WIN / 2 {
+        texts.wins.to_string()
+    } else if eval <= -pyc::WIN / 2 {
+        texts.loses.to_string()
+    } else {
+        format!("{:+}", eval)
+    }
+}
+
+impl Polycentrics {
+    /// Bring the analysis up to the shown position and game, returns if it changed.
+    pub(crate) fn analyse(&mut self) -> bool {
+        let analysis = match self.analysis.as_mut() {
+            Some(analysis) => analysis,
+            None => return false,
+        };
+
+        let (record, at, board) = match &self.viewer {
+            Some(viewer) => (&viewer.record, viewer.at, &viewer.board),
+            None => (&self.record, self.record.moves.len(), &self.board),
+        };
+
+        let mut changed = false;
+
+        if analysis.position.as_deref() != Some(&record.moves[..at]) {
+            analysis.position = Some(record.moves[..at].to_vec());
+            analysis.search(board);
+            changed = true;
+        }
+
+        if analysis.game.as_deref() != Some(&record.moves[..]) {
+            analysis.game = Some(record.moves.clone());
+            analysis.graph(record);
+            changed = true;
+        }
+
+        changed
+    }
+
+    /// Render the principal variation to SVG, as faint curves.
+    pub(crate) fn analysis_svg(&self) -> Html {
+        let analysis = match &self.analysis {
+            Some(analysis) => analysis,
+            None => return html! {},
+        };
+
+        html! {
+            <g class="analysis-line">{
+                analysis
+                    .line
+                    .iter()
+                    .map(|curve| html! {
+                        <path
+                            class="curve"
+                            d=format!("M {} {} Q {} {} {} {}", curve.start.x, curve.start.y, curve.mid.x, curve.mid.y, curve.end.x, curve.end.y)
+                        />
+                    })
+                    .collect::<Html>()
+            }</g>
+        }
+    }
+
+    /// [`Html`] toggle of the analysis, with the best tiles and the graph of the game.
+    // online games are real games, and the server holds their moves
+    pub(crate) fn analysis_view(&self) -> Html {
+        if let Mode::Online = self.props.mode {
+            return html! {};
+        }
+
+        let texts = self.texts();
+        let toggle = html! {
+            <label>
+                <input
+                    type="checkbox"
+                    checked=self.analysis.is_some()
+                    onclick=self.link.callback(|_| GameMsg::Analysis)
+                />
+                { " " }{ texts.analysis }
+            </label>
+        };
+
+        let analysis = match &self.analysis {
+            Some(analysis) => analysis,
+            None => return html! { <div class="analysis">{ toggle }</div> },
+        };
+
+        let board = self.shown();
+        let options = board.options();
+
+        html! {
+            <div class="analysis">
+                { toggle }
+                {
+                    if analysis.lines.is_empty() {
+                        html! {}
+                    } else {
+                        html! {
+                            <>
+                                <span>{ fill(texts.best_tiles, &[&player_name(texts, board.active)]) }</span>
+                                <ol class="analysis-lines">{
+                                    analysis
+                                        .lines
+                                        .iter()
+                                        // the lines are one render behind a new position
+                                        .filter_map(|(tile, eval)| options.get(*tile).map(|curve| (curve, eval)))
+                                        .map(|(curve, eval)| html! {
+                                            <li>{ format!("{}: {}", a11y::tile_label(texts, curve), eval_label(texts, *eval)) }</li>
+                                        })
+                                        .collect::<Html>()
+                                }</ol>
+                            </>
+                        }
+                    }
+                }
+                { self.graph_view(analysis) }
+            </div>
+        }
+    }
+
+    /// [`Html`] graph of the evaluation over the moves of the game, the shown move marked.
+    fn graph_view(&self, analysis: &Analysis) -> Html {
+        let at = match &self.viewer {
+            Some(viewer) => viewer.at,
+            None => self.record.moves.len(),
+        };
+
+        // gamma up, delta down
+        let points = analysis
+            .graph
+            .iter()
+            .enumerate()
+            .map(|(i, &eval)| format!("{},{}", i, -eval.clamp(-GRAPH_RANGE, GRAPH_RANGE)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let width = analysis.graph.len().saturating_sub(1).max(1);
+
+        html! {
+            <svg
+                class="analysis-graph"
+                xmlns="http://www.w3.org/2000/svg"
+                role="img"
+                aria-label=fill(self.texts().eval_graph, &[&player_name(self.texts(), pyc::Player::Gamma)])
+                viewBox=format!("0 {} {} {}", -GRAPH_RANGE, width, 2 * GRAPH_RANGE)
+                preserveAspectRatio="none"
+            >
+                <line class="analysis-zero" x1="0" y1="0" x2=width.to_string() y2="0" vector-effect="non-scaling-stroke"/>
+                <line
+                    class="analysis-at"
+                    x1=at.to_string() y1=(-GRAPH_RANGE).to_string()
+                    x2=at.to_string() y2=GRAPH_RANGE.to_string()
+                    vector-effect="non-scaling-stroke"
+                />
+                <polyline points=points vector-effect="non-scaling-stroke"/>
+            </svg>
+        }
+    }
+}
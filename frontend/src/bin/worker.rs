@@ -0,0 +1,12 @@
+//! Entry of the Web Worker, loaded by the app as `worker.js`.
+
+use polycentrics_frontend::worker::SearchWorker;
+use yew::agent::Threaded;
+
+fn main() {
+    // this traces on panic in the js console
+    #[cfg(debug_assertions)]
+    std::panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    SearchWorker::register();
+}
@@ -0,0 +1,248 @@
+//! Zoom and pan of the board view.
+
+use super::{GameMsg, Polycentrics};
+use backend::prelude as pyc;
+use wasm_bindgen::JsCast;
+use yew::{prelude::*, web_sys::Element};
+
+/// The square of the board in view, in board units, as the SVG `viewBox`.
+pub(crate) struct Camera {
+    x: f64,
+    y: f64,
+    size: f64,
+    // pointers down on the board, as id and position, see `position`
+    pointers: Vec<(i32, f64, f64)>,
+}
+
+/// Messages of the [`Camera`].
+// positions are fractions of the board view, so the camera needs no DOM
+pub(crate) enum CameraMsg {
+    // zoom by the factor, around the position
+    Zoom(f64, f64, f64),
+    Down(i32, f64, f64),
+    Move(i32, f64, f64),
+    Up(i32),
+    FitPath,
+    FitBoard,
+}
+
+// zoom of one wheel notch
+const ZOOM_STEP: f64 = 1.2;
+
+// least points in view, about the size of the largest tile
+const MIN_SIZE: f64 = 4.0;
+
+impl Camera {
+    /// The whole board with a border of one unit.
+    pub(crate) fn board(size: usize) -> Self {
+        Self {
+            x: -1.0,
+            y: -1.0,
+            size: size as f64 + 1.0,
+            pointers: Vec::new(),
+        }
+    }
+
+    /// The `viewBox` of the board SVG.
+    pub(crate) fn view_box(&self) -> String {
+        format!("{} {} {} {}", self.x, self.y, self.size, self.size)
+    }
+
+    /// Frame the bounding box of the path and the arrow, with a border of one unit.
+    /// The whole board if there is no path yet.
+    fn fit(&mut self, board: &pyc::Board) {
+        if board.path.is_empty() {
+            *self = Self::board(board.points.len());
+            return;
+        }
+
+        // the control points bound the curve
+        let points = board
+            .path
+            .iter()
+            .flat_map(|curve| vec![curve.start, curve.mid, curve.end])
+            .chain(std::iter::once(board.arrow.pos));
+
+        let (mut min, mut max) = ((f64::MAX, f64::MAX), (f64::MIN, f64::MIN));
+        for point in points {
+            let (x, y) = (point.x as f64, point.y as f64);
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+
+        // square, so the board is not stretched
+        let size = (max.0 - min.0).max(max.1 - min.1).max(MIN_SIZE) + 2.0;
+        self.x = (min.0 + max.0 - size) / 2.0;
+        self.y = (min.1 + max.1 - size) / 2.0;
+        self.size = size;
+    }
+
+    /// Scale the view by `factor`, keeping the board point at the position in place.
+    fn zoom(&mut self, factor: f64, x: f64, y: f64, board: usize) {
+        // not further out than twice the board
+        let size = (self.size * factor).clamp(MIN_SIZE, 2.0 * (board as f64 + 1.0));
+
+        self.x += x * (self.size - size);
+        self.y += y * (self.size - size);
+        self.size = size;
+    }
+
+    /// Move the view along with a pointer moved by a fraction of it.
+    fn pan(&mut self, dx: f64, dy: f64) {
+        self.x -= dx * self.size;
+        self.y -= dy * self.size;
+    }
+
+    /// Update the camera, returns if the view changed.
+    // one pointer drags, two pinch
+    fn update(&mut self, msg: CameraMsg, board: &pyc::Board) -> bool {
+        let size = board.points.len();
+
+        match msg {
+            CameraMsg::Zoom(factor, x, y) => self.zoom(factor, x, y, size),
+            CameraMsg::Down(id, x, y) => {
+                self.pointers.retain(|(other, _, _)| *other != id);
+                self.pointers.push((id, x, y));
+                return false;
+            }
+            CameraMsg::Move(id, x, y) => {
+                let before = self.pointers.clone();
+                match self.pointers.iter_mut().find(|(other, _, _)| *other == id) {
+                    Some(pointer) => *pointer = (id, x, y),
+                    // hovering, no button pressed
+                    None => return false,
+                }
+
+                let after = self.pointers.clone();
+                match (before.as_slice(), after.as_slice()) {
+                    ([(_, x1, y1)], [(_, x2, y2)]) => self.pan(x2 - x1, y2 - y1),
+                    ([a1, b1], [a2, b2]) => {
+                        let mid = |a: &(i32, f64, f64), b: &(i32, f64, f64)| {
+                            ((a.1 + b.1) / 2.0, (a.2 + b.2) / 2.0)
+                        };
+                        let dist =
+                            |a: &(i32, f64, f64), b: &(i32, f64, f64)| (a.1 - b.1).hypot(a.2 - b.2);
+
+                        let (mx1, my1) = mid(a1, b1);
+                        let (mx2, my2) = mid(a2, b2);
+
+                        self.pan(mx2 - mx1, my2 - my1);
+                        if dist(a2, b2) > 0.0 {
+                            self.zoom(dist(a1, b1) / dist(a2, b2), mx2, my2, size);
+                        }
+                    }
+                    _ => return false,
+                }
+            }
+            CameraMsg::Up(id) => {
+                self.pointers.retain(|(other, _, _)| *other != id);
+                return false;
+            }
+            CameraMsg::FitPath => self.fit(board),
+            CameraMsg::FitBoard => *self = Self::board(size),
+        }
+
+        true
+    }
+}
+
+/// Position of a pointer as fractions of the element listening to it.
+fn position(event: &MouseEvent) -> (f64, f64) {
+    let rect = match event
+        .current_target()
+        .and_then(|target| target.dyn_into::<Element>().ok())
+    {
+        Some(element) => element.get_bounding_client_rect(),
+        None => return (0.5, 0.5),
+    };
+
+    (
+        (event.client_x() as f64 - rect.left()) / rect.width().max(1.0),
+        (event.client_y() as f64 - rect.top()) / rect.height().max(1.0),
+    )
+}
+
+impl Polycentrics {
+    /// Updates the view of the board based on a [`CameraMsg`].
+    pub(crate) fn update_camera(&mut self, msg: CameraMsg) -> ShouldRender {
+        // frames the viewed board while stepping through a record
+        let board = match &self.viewer {
+            Some(viewer) => &viewer.board,
+            None => &self.board,
+        };
+
+        self.camera.update(msg, board)
+    }
+
+    /// Listeners of the board SVG to pointers going down, moving, up and being cancelled.
+    pub(crate) fn camera_listeners(&self) -> [Callback<PointerEvent>; 4] {
+        let up = || {
+            self.link
+                .callback(|event: PointerEvent| GameMsg::Camera(CameraMsg::Up(event.pointer_id())))
+        };
+
+        [
+            self.link.callback(|event: PointerEvent| {
+                // keep dragging when leaving the board
+                if let Some(element) = event
+                    .current_target()
+                    .and_then(|target| target.dyn_into::<Element>().ok())
+                {
+                    let _ = element.set_pointer_capture(event.pointer_id());
+                }
+
+                let (x, y) = position(&event);
+                GameMsg::Camera(CameraMsg::Down(event.pointer_id(), x, y))
+            }),
+            self.link.callback(|event: PointerEvent| {
+                let (x, y) = position(&event);
+                GameMsg::Camera(CameraMsg::Move(event.pointer_id(), x, y))
+            }),
+            up(),
+            up(),
+        ]
+    }
+
+    /// Listener of the wheel over the board, zooming around the pointer.
+    pub(crate) fn camera_wheel(&self) -> Callback<WheelEvent> {
+        self.link.callback(|event: WheelEvent| {
+            // the page would scroll
+            event.prevent_default();
+
+            let (x, y) = position(&event);
+            let factor = if event.delta_y() > 0.0 {
+                ZOOM_STEP
+            } else {
+                1.0 / ZOOM_STEP
+            };
+
+            GameMsg::Camera(CameraMsg::Zoom(factor, x, y))
+        })
+    }
+
+    /// [`Html`] buttons to frame the board.
+    pub(crate) fn camera_view(&self) -> Html {
+        let texts = self.texts();
+
+        html! {
+            <div class="camera">
+                <button
+                    class="action-button"
+                    onclick=self.link.callback(|_| GameMsg::Camera(CameraMsg::Zoom(1.0 / ZOOM_STEP, 0.5, 0.5)))
+                >{ texts.zoom_in }</button>
+                <button
+                    class="action-button"
+                    onclick=self.link.callback(|_| GameMsg::Camera(CameraMsg::Zoom(ZOOM_STEP, 0.5, 0.5)))
+                >{ texts.zoom_out }</button>
+                <button
+                    class="action-button"
+                    onclick=self.link.callback(|_| GameMsg::Camera(CameraMsg::FitPath))
+                >{ texts.fit_path }</button>
+                <button
+                    class="action-button"
+                    onclick=self.link.callback(|_| GameMsg::Camera(CameraMsg::FitBoard))
+                >{ texts.whole_board }</button>
+            </div>
+        }
+    }
+}
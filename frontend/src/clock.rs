@@ -0,0 +1,81 @@
+use super::{i18n::Texts, menu::selected, GameMsg, Polycentrics};
+use backend::prelude as pyc;
+use yew::prelude::*;
+
+/// How often the clocks are redrawn and checked for their flag.
+pub(crate) const TICK: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Time controls to choose from before the first step.
+// a function and not a const because the constructors are not `const fn`
+pub(crate) fn time_controls(texts: &Texts) -> [(&'static str, Option<pyc::TimeControl>); 4] {
+    [
+        (texts.untimed, None),
+        ("3+2 Fischer", Some(pyc::TimeControl::fischer(3, 2))),
+        ("5+3 Fischer", Some(pyc::TimeControl::fischer(5, 3))),
+        ("10+5 Bronstein", Some(pyc::TimeControl::bronstein(10, 5))),
+    ]
+}
+
+/// Milliseconds of the browser clock.
+pub(crate) fn now() -> pyc::Millis {
+    js_sys::Date::now() as pyc::Millis
+}
+
+impl Polycentrics {
+    /// [`Html`] view of the time left of both players.
+    pub(crate) fn clock_view(&self) -> Html {
+        let clock = match &self.clock {
+            Some(clock) => clock,
+            None => return html! {},
+        };
+
+        let now = now();
+        let texts = self.texts();
+
+        let view = |player: pyc::Player, name: &str| {
+            let running = matches!(clock.running, Some((running, _)) if running == player);
+
+            html! {
+                <span class=classes!("clock", running.then_some("clock-running"))>
+                    { format!("{} {}", name, format_time(clock.left(player, now))) }
+                </span>
+            }
+        };
+
+        html! {
+            <div class="clocks">
+                { view(pyc::Player::Gamma, texts.players[0]) }
+                { view(pyc::Player::Delta, texts.players[1]) }
+            </div>
+        }
+    }
+
+    /// [`Html`] select of the [`time_controls`], only before the first step.
+    pub(crate) fn time_control_view(&self) -> Html {
+        let current = self.clock.as_ref().map(|clock| clock.control);
+
+        html! {
+            <select
+                class="time-control"
+                disabled={ self.board.step > 1 }
+                onchange=self.link.callback(|data| GameMsg::TimeControl(selected(data)))
+            >{
+                time_controls(self.texts())
+                    .iter()
+                    .map(|(name, control)| html! {
+                        <option selected=*control == current>{ name }</option>
+                    })
+                    .collect::<Html>()
+            }</select>
+        }
+    }
+}
+
+/// `m:ss`, or `s.t` in the last ten seconds.
+fn format_time(millis: pyc::Millis) -> String {
+    if millis < 10_000 {
+        format!("{}.{}", millis / 1000, millis / 100 % 10)
+    } else {
+        format!("{}:{:02}", millis / 60_000, millis / 1000 % 60)
+    }
+}
@@ -0,0 +1,741 @@
+//! Translations of the UI text, one [`Texts`] catalogue per [`Locale`].
+
+use super::Polycentrics;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use yew::{
+    format::Json,
+    services::{storage::Area, StorageService},
+};
+
+/// Language of the UI.
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub(crate) enum Locale {
+    #[default]
+    En,
+    De,
+    It,
+}
+
+// key in `localStorage`
+const KEY: &str = "polycentrics.locale";
+
+impl Locale {
+    pub(crate) const ALL: [Locale; 3] = [Locale::En, Locale::De, Locale::It];
+
+    /// Name of the language in itself, for the switcher.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::De => "Deutsch",
+            Locale::It => "Italiano",
+        }
+    }
+
+    /// Language tag of the page.
+    pub(crate) fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+            Locale::It => "it",
+        }
+    }
+
+    pub(crate) fn texts(self) -> &'static Texts {
+        match self {
+            Locale::En => &EN,
+            Locale::De => &DE,
+            Locale::It => &IT,
+        }
+    }
+
+    /// The language chosen on an earlier visit, or else the one of the browser.
+    pub(crate) fn load() -> Self {
+        if let Ok(storage) = StorageService::new(Area::Local) {
+            if let Json(Ok(locale)) = storage.restore(KEY) {
+                return locale;
+            }
+        }
+
+        // like `de-CH`, only the language counts
+        let language = yew::utils::window()
+            .navigator()
+            .language()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|locale| language.starts_with(locale.code()))
+            .unwrap_or_default()
+    }
+
+    /// Keep the language for the next visit, if `localStorage` is available.
+    pub(crate) fn save(self) {
+        if let Ok(mut storage) = StorageService::new(Area::Local) {
+            storage.store(KEY, Json(&self));
+        }
+    }
+
+    /// Tell the browser and screen readers the language of the page.
+    pub(crate) fn apply(self) {
+        if let Some(html) = yew::utils::document().document_element() {
+            let _ = html.set_attribute("lang", self.code());
+        }
+    }
+}
+
+/// Put `args` into the `{0}`, `{1}`, ... of `text`,
+/// numbered so translations can change their order.
+pub(crate) fn fill(text: &str, args: &[&dyn fmt::Display]) -> String {
+    args.iter()
+        .enumerate()
+        .fold(text.to_string(), |text, (i, arg)| {
+            text.replace(&format!("{{{}}}", i), &arg.to_string())
+        })
+}
+
+impl Polycentrics {
+    /// The catalogue of the language of the game.
+    pub(crate) fn texts(&self) -> &'static Texts {
+        self.props.locale.texts()
+    }
+}
+
+/// Text of a lesson of the tutorial.
+pub(crate) struct Lesson {
+    pub title: &'static str,
+    pub text: &'static str,
+    // shown once the tile is set
+    pub done: &'static str,
+}
+
+/// Every text of the UI in one language, placeholders are filled with [`fill`].
+// a struct and not a map, so a missing translation does not compile
+pub(crate) struct Texts {
+    // # names
+    pub left: &'static str,
+    pub right: &'static str,
+    // the turn and radius of a tile
+    pub tile: &'static str,
+    pub tile_backward: &'static str,
+    pub north: &'static str,
+    pub east: &'static str,
+    pub south: &'static str,
+    pub west: &'static str,
+    pub empty: &'static str,
+    // names of `pyc::Player`, Gamma first
+    pub players: [&'static str; 2],
+    // names of `theme::Base::ALL`
+    pub themes: [&'static str; 4],
+    // names of `menu::mode()`
+    pub modes: [&'static str; 6],
+    // names of `menu::STRENGTHS`
+    pub strengths: [&'static str; 3],
+    pub twice: &'static str,
+    pub untimed: &'static str,
+
+    // # screen readers
+    pub board_label: &'static str,
+    pub point_label: &'static str,
+    pub won: &'static str,
+    pub drawn: &'static str,
+    pub captured: &'static str,
+    pub score: &'static str,
+    pub offers_draw: &'static str,
+    pub offer_declined: &'static str,
+    pub took_back: &'static str,
+    pub capture_title: &'static str,
+
+    // # game
+    pub step: &'static str,
+    pub tiles_left: &'static str,
+    pub won_game: &'static str,
+    pub draw: &'static str,
+    pub play_again: &'static str,
+    pub rotate_pad: &'static str,
+    pub turn_left: &'static str,
+    pub turn_right: &'static str,
+    pub hint: &'static str,
+    pub undo: &'static str,
+    pub share: &'static str,
+    pub shared: &'static str,
+    pub review: &'static str,
+    pub thinking: &'static str,
+    pub thinking_ahead: &'static str,
+    pub resign: &'static str,
+    pub offer_draw: &'static str,
+    pub accept: &'static str,
+    pub decline: &'static str,
+
+    // # board view
+    pub zoom_in: &'static str,
+    pub zoom_out: &'static str,
+    pub fit_path: &'static str,
+    pub whole_board: &'static str,
+    pub theme: &'static str,
+    pub reset_colours: &'static str,
+    pub colour_blind: &'static str,
+    pub export_svg: &'static str,
+
+    // # viewer
+    pub viewer_position: &'static str,
+    pub first: &'static str,
+    pub back: &'static str,
+    pub forward: &'static str,
+    pub last: &'static str,
+    pub continue_here: &'static str,
+    pub close: &'static str,
+
+    // # analysis
+    pub analysis: &'static str,
+    pub best_tiles: &'static str,
+    pub wins: &'static str,
+    pub loses: &'static str,
+    pub eval_graph: &'static str,
+
+    // # puzzles and tutorial
+    pub puzzle_title: &'static str,
+    // the points of `Puzzle::points`
+    pub puzzle_goal: &'static str,
+    pub no_puzzles: &'static str,
+    pub find_best: &'static str,
+    pub not_best: &'static str,
+    pub solved: &'static str,
+    pub previous: &'static str,
+    pub retry: &'static str,
+    pub next: &'static str,
+    pub lesson_title: &'static str,
+    pub not_allowed: &'static str,
+    pub lessons: [Lesson; 4],
+
+    // # menu
+    pub menu: &'static str,
+    pub language: &'static str,
+    pub board_size: &'static str,
+    pub tiles: &'static str,
+    pub mode: &'static str,
+    pub bot_plays: &'static str,
+    pub strength: &'static str,
+    pub start: &'static str,
+    pub board: &'static str,
+    pub score_column: &'static str,
+    pub step_column: &'static str,
+    pub resume: &'static str,
+    pub view: &'static str,
+    pub delete: &'static str,
+
+    // # online
+    pub server: &'static str,
+    pub name: &'static str,
+    pub game: &'static str,
+    pub size: &'static str,
+    pub connect: &'static str,
+    pub lobby: &'static str,
+    pub create: &'static str,
+    pub join: &'static str,
+    pub watch: &'static str,
+    pub connecting: &'static str,
+    pub connected: &'static str,
+    pub disconnected: &'static str,
+    pub connection_failed: &'static str,
+    pub invalid_game: &'static str,
+    pub invalid_message: &'static str,
+    pub games: &'static str,
+    pub waiting: &'static str,
+    pub watching: &'static str,
+    pub abandoned: &'static str,
+    pub returned: &'static str,
+    pub versus: &'static str,
+    pub resigned: &'static str,
+    pub draw_agreed: &'static str,
+    pub draw_declined: &'static str,
+}
+
+const EN: Texts = Texts {
+    left: "left",
+    right: "right",
+    tile: "Turn {0}, radius {1}",
+    tile_backward: "Turn {0}, radius {1}, backward",
+    north: "north",
+    east: "east",
+    south: "south",
+    west: "west",
+    empty: "empty",
+    players: ["Gamma", "Delta"],
+    themes: ["Light", "Dark", "High contrast", "Print"],
+    modes: [
+        "Hot seat",
+        "Versus bot",
+        "Bot versus bot",
+        "Online",
+        "Puzzles",
+        "Tutorial",
+    ],
+    strengths: ["Easy", "Medium", "Hard"],
+    twice: "{0} twice",
+    untimed: "Untimed",
+
+    board_label: "Board of {0} by {0} points, the arrow is at {1}, {2} facing {3}",
+    point_label: "Point {0}, {1}: {2}",
+    won: "{0} won.",
+    drawn: "The game is a draw.",
+    captured: "Captured {0} points.",
+    score: "Score {0} to {1}.",
+    offers_draw: "{0} offers a draw",
+    offer_declined: "The draw offer was declined.",
+    took_back: "Took back moves, {0} to play. Score {1} to {2}.",
+    capture_title: "Step {0}: {1} captured {2} points",
+
+    step: "Step {0}",
+    tiles_left: "{0} tiles left",
+    won_game: "{0} won!",
+    draw: "Draw!",
+    play_again: "Play again",
+    rotate_pad: "Rotate with arrow",
+    turn_left: "Turn left",
+    turn_right: "Turn right",
+    hint: "Hint",
+    undo: "Undo",
+    share: "Share",
+    shared: "The link to the game is in the address bar.",
+    review: "Review",
+    thinking: "Thinking",
+    thinking_ahead: "Thinking, {0} steps ahead",
+    resign: "Resign",
+    offer_draw: "Offer draw",
+    accept: "Accept",
+    decline: "Decline",
+
+    zoom_in: "Zoom in",
+    zoom_out: "Zoom out",
+    fit_path: "Fit to path",
+    whole_board: "Whole board",
+    theme: "Theme",
+    reset_colours: "Reset colours",
+    colour_blind: "Colour-blind palette",
+    export_svg: "Export SVG",
+
+    viewer_position: "Step {0}, move {1} of {2}",
+    first: "First",
+    back: "Back",
+    forward: "Forward",
+    last: "Last",
+    continue_here: "Continue from here",
+    close: "Close",
+
+    analysis: "Analysis",
+    best_tiles: "Best tiles for {0}",
+    wins: "wins",
+    loses: "loses",
+    eval_graph: "Evaluation for {0} after every move",
+
+    puzzle_title: "Puzzle {0} of {1}: {2}",
+    puzzle_goal: "Capture {0} points",
+    no_puzzles: "No puzzles found.",
+    find_best: "{0} to play, find the best tile.",
+    not_best: "Not the best tile, try again.",
+    solved: "Solved!",
+    previous: "Previous",
+    retry: "Retry",
+    next: "Next",
+    lesson_title: "Lesson {0} of {1}: {2}",
+    not_allowed: "Not this tile, choose one of the tiles left enabled.",
+    lessons: [
+        Lesson {
+            title: "Choosing tiles",
+            text: "The players take turns. On your turn, set one of the tiles left on the pad. \
+                Every tile can be set only once. Set any tile.",
+            done: "The tile is gone from the pad, now the other player chooses.",
+        },
+        Lesson {
+            title: "The arrow",
+            text: "A tile starts at the arrow and is turned like it. \
+                The arrow then moves to the end of the tile and turns left or right with it. \
+                Set a tile turning right.",
+            done:
+                "The arrow moved to the end of the tile and now faces to the right of where it did.",
+        },
+        Lesson {
+            title: "Closing a loop",
+            text: "If a tile crosses the path, it closes a loop. \
+                Every free point inside the loop is captured by the player who set the tile. \
+                Close the loop.",
+            done:
+                "The points inside are yours. Whoever has more points once the tiles run out wins, \
+                on a tie the second player.",
+        },
+        Lesson {
+            title: "The border",
+            text: "A tile ending outside the board loses the game at once. \
+                Hover over the tiles to see where they end, the ones outside are red. \
+                Set a tile that stays on the board.",
+            done: "The arrow is still on the board. You know the rules now, have a good game!",
+        },
+    ],
+
+    menu: "Menu",
+    language: "Language",
+    board_size: "Board size",
+    tiles: "Tiles",
+    mode: "Mode",
+    bot_plays: "Bot plays",
+    strength: "Strength",
+    start: "Start",
+    board: "Board",
+    score_column: "Score",
+    step_column: "Step",
+    resume: "Resume",
+    view: "View",
+    delete: "Delete",
+
+    server: "Server",
+    name: "Name",
+    game: "Game",
+    size: "Size",
+    connect: "Connect",
+    lobby: "Lobby",
+    create: "Create",
+    join: "Join",
+    watch: "Watch ({0})",
+    connecting: "Connecting...",
+    connected: "Connected",
+    disconnected: "Disconnected",
+    connection_failed: "Connection failed",
+    invalid_game: "Invalid game: {0}",
+    invalid_message: "Invalid server message",
+    games: "{0} games",
+    waiting: "Game {0}, waiting for an opponent",
+    watching: "Watching game {0} at step {1}",
+    abandoned: "{0} left, the seat is lost in {1} seconds",
+    returned: "{0} returned",
+    versus: "{0} (Gamma) vs. {1} (Delta)",
+    resigned: "{0} resigned",
+    draw_agreed: "Draw agreed",
+    draw_declined: "{0} declined the draw",
+};
+
+const DE: Texts = Texts {
+    left: "links",
+    right: "rechts",
+    tile: "Nach {0} drehen, Radius {1}",
+    tile_backward: "Nach {0} drehen, Radius {1}, rückwärts",
+    north: "Norden",
+    east: "Osten",
+    south: "Süden",
+    west: "Westen",
+    empty: "frei",
+    players: ["Gamma", "Delta"],
+    themes: ["Hell", "Dunkel", "Hoher Kontrast", "Druck"],
+    modes: [
+        "Am selben Gerät",
+        "Gegen den Bot",
+        "Bot gegen Bot",
+        "Online",
+        "Rätsel",
+        "Einführung",
+    ],
+    strengths: ["Leicht", "Mittel", "Schwer"],
+    twice: "{0} doppelt",
+    untimed: "Ohne Zeit",
+
+    board_label: "Brett mit {0} mal {0} Punkten, der Pfeil steht auf {1}, {2} und zeigt nach {3}",
+    point_label: "Punkt {0}, {1}: {2}",
+    won: "{0} hat gewonnen.",
+    drawn: "Das Spiel endet unentschieden.",
+    captured: "{0} Punkte erobert.",
+    score: "Stand {0} zu {1}.",
+    offers_draw: "{0} bietet ein Remis an",
+    offer_declined: "Das Remisangebot wurde abgelehnt.",
+    took_back: "Züge zurückgenommen, {0} ist am Zug. Stand {1} zu {2}.",
+    capture_title: "Schritt {0}: {1} eroberte {2} Punkte",
+
+    step: "Schritt {0}",
+    tiles_left: "{0} Plättchen übrig",
+    won_game: "{0} hat gewonnen!",
+    draw: "Remis!",
+    play_again: "Nochmal spielen",
+    rotate_pad: "Mit dem Pfeil drehen",
+    turn_left: "Links drehen",
+    turn_right: "Rechts drehen",
+    hint: "Tipp",
+    undo: "Rückgängig",
+    share: "Teilen",
+    shared: "Der Link zum Spiel steht in der Adressleiste.",
+    review: "Nachspielen",
+    thinking: "Denkt nach",
+    thinking_ahead: "Denkt {0} Schritte voraus",
+    resign: "Aufgeben",
+    offer_draw: "Remis anbieten",
+    accept: "Annehmen",
+    decline: "Ablehnen",
+
+    zoom_in: "Vergrößern",
+    zoom_out: "Verkleinern",
+    fit_path: "Pfad einpassen",
+    whole_board: "Ganzes Brett",
+    theme: "Design",
+    reset_colours: "Farben zurücksetzen",
+    colour_blind: "Palette für Farbenblinde",
+    export_svg: "SVG exportieren",
+
+    viewer_position: "Schritt {0}, Zug {1} von {2}",
+    first: "Anfang",
+    back: "Zurück",
+    forward: "Vor",
+    last: "Ende",
+    continue_here: "Von hier weiterspielen",
+    close: "Schließen",
+
+    analysis: "Analyse",
+    best_tiles: "Beste Plättchen für {0}",
+    wins: "gewinnt",
+    loses: "verliert",
+    eval_graph: "Bewertung für {0} nach jedem Zug",
+
+    puzzle_title: "Rätsel {0} von {1}: {2}",
+    puzzle_goal: "Erobere {0} Punkte",
+    no_puzzles: "Keine Rätsel gefunden.",
+    find_best: "{0} ist am Zug, finde das beste Plättchen.",
+    not_best: "Nicht das beste Plättchen, versuch es noch einmal.",
+    solved: "Gelöst!",
+    previous: "Vorheriges",
+    retry: "Nochmal",
+    next: "Nächstes",
+    lesson_title: "Lektion {0} von {1}: {2}",
+    not_allowed: "Nicht dieses Plättchen, wähle eines der aktiven.",
+    lessons: [
+        Lesson {
+            title: "Plättchen wählen",
+            text: "Die Spieler sind abwechselnd am Zug. Wer am Zug ist, legt eines der übrigen \
+                Plättchen. Jedes Plättchen kann nur einmal gelegt werden. Lege ein beliebiges Plättchen.",
+            done: "Das Plättchen ist weg, jetzt wählt der andere Spieler.",
+        },
+        Lesson {
+            title: "Der Pfeil",
+            text: "Ein Plättchen beginnt am Pfeil und ist wie er gedreht. \
+                Danach rückt der Pfeil an das Ende des Plättchens und dreht sich mit ihm nach links \
+                oder rechts. Lege ein Plättchen, das nach rechts dreht.",
+            done: "Der Pfeil ist an das Ende des Plättchens gerückt und zeigt jetzt nach rechts \
+                von seiner alten Richtung.",
+        },
+        Lesson {
+            title: "Eine Schleife schließen",
+            text: "Kreuzt ein Plättchen den Pfad, schließt es eine Schleife. \
+                Jeder freie Punkt in der Schleife gehört dann dem Spieler, der das Plättchen gelegt hat. \
+                Schließe die Schleife.",
+            done: "Die Punkte in der Schleife gehören dir. Wer mehr Punkte hat, wenn die Plättchen \
+                ausgehen, gewinnt, bei Gleichstand der zweite Spieler.",
+        },
+        Lesson {
+            title: "Der Rand",
+            text: "Wer ein Plättchen außerhalb des Bretts enden lässt, verliert sofort. \
+                Fahre über die Plättchen, um zu sehen, wo sie enden, die außerhalb sind rot. \
+                Lege ein Plättchen, das auf dem Brett bleibt.",
+            done: "Der Pfeil ist noch auf dem Brett. Jetzt kennst du die Regeln, viel Spaß!",
+        },
+    ],
+
+    menu: "Menü",
+    language: "Sprache",
+    board_size: "Brettgröße",
+    tiles: "Plättchen",
+    mode: "Modus",
+    bot_plays: "Bot spielt",
+    strength: "Stärke",
+    start: "Starten",
+    board: "Brett",
+    score_column: "Stand",
+    step_column: "Schritt",
+    resume: "Fortsetzen",
+    view: "Ansehen",
+    delete: "Löschen",
+
+    server: "Server",
+    name: "Name",
+    game: "Spiel",
+    size: "Größe",
+    connect: "Verbinden",
+    lobby: "Lobby",
+    create: "Erstellen",
+    join: "Beitreten",
+    watch: "Zuschauen ({0})",
+    connecting: "Verbinde...",
+    connected: "Verbunden",
+    disconnected: "Getrennt",
+    connection_failed: "Verbindung fehlgeschlagen",
+    invalid_game: "Ungültiges Spiel: {0}",
+    invalid_message: "Ungültige Nachricht des Servers",
+    games: "{0} Spiele",
+    waiting: "Spiel {0}, warte auf einen Gegner",
+    watching: "Du schaust Spiel {0} bei Schritt {1} zu",
+    abandoned: "{0} ist gegangen, der Platz verfällt in {1} Sekunden",
+    returned: "{0} ist zurück",
+    versus: "{0} (Gamma) gegen {1} (Delta)",
+    resigned: "{0} hat aufgegeben",
+    draw_agreed: "Remis vereinbart",
+    draw_declined: "{0} hat das Remis abgelehnt",
+};
+
+const IT: Texts = Texts {
+    left: "sinistra",
+    right: "destra",
+    tile: "Gira a {0}, raggio {1}",
+    tile_backward: "Gira a {0}, raggio {1}, all'indietro",
+    north: "nord",
+    east: "est",
+    south: "sud",
+    west: "ovest",
+    empty: "libero",
+    players: ["Gamma", "Delta"],
+    themes: ["Chiaro", "Scuro", "Alto contrasto", "Stampa"],
+    modes: [
+        "Stesso dispositivo",
+        "Contro il bot",
+        "Bot contro bot",
+        "Online",
+        "Problemi",
+        "Tutorial",
+    ],
+    strengths: ["Facile", "Medio", "Difficile"],
+    twice: "{0} doppio",
+    untimed: "Senza tempo",
+
+    board_label: "Tavola di {0} per {0} punti, la freccia è su {1}, {2} rivolta a {3}",
+    point_label: "Punto {0}, {1}: {2}",
+    won: "{0} ha vinto.",
+    drawn: "La partita è patta.",
+    captured: "Conquistati {0} punti.",
+    score: "Punteggio {0} a {1}.",
+    offers_draw: "{0} propone la patta",
+    offer_declined: "La proposta di patta è stata rifiutata.",
+    took_back: "Mosse annullate, tocca a {0}. Punteggio {1} a {2}.",
+    capture_title: "Passo {0}: {1} ha conquistato {2} punti",
+
+    step: "Passo {0}",
+    tiles_left: "{0} tessere rimaste",
+    won_game: "{0} ha vinto!",
+    draw: "Patta!",
+    play_again: "Gioca ancora",
+    rotate_pad: "Ruota con la freccia",
+    turn_left: "Gira a sinistra",
+    turn_right: "Gira a destra",
+    hint: "Suggerimento",
+    undo: "Annulla",
+    share: "Condividi",
+    shared: "Il link alla partita è nella barra degli indirizzi.",
+    review: "Rivedi",
+    thinking: "Sta pensando",
+    thinking_ahead: "Sta pensando, {0} passi avanti",
+    resign: "Abbandona",
+    offer_draw: "Proponi patta",
+    accept: "Accetta",
+    decline: "Rifiuta",
+
+    zoom_in: "Ingrandisci",
+    zoom_out: "Riduci",
+    fit_path: "Adatta al percorso",
+    whole_board: "Tavola intera",
+    theme: "Tema",
+    reset_colours: "Ripristina colori",
+    colour_blind: "Palette per daltonici",
+    export_svg: "Esporta SVG",
+
+    viewer_position: "Passo {0}, mossa {1} di {2}",
+    first: "Inizio",
+    back: "Indietro",
+    forward: "Avanti",
+    last: "Fine",
+    continue_here: "Continua da qui",
+    close: "Chiudi",
+
+    analysis: "Analisi",
+    best_tiles: "Migliori tessere per {0}",
+    wins: "vince",
+    loses: "perde",
+    eval_graph: "Valutazione per {0} dopo ogni mossa",
+
+    puzzle_title: "Problema {0} di {1}: {2}",
+    puzzle_goal: "Conquista {0} punti",
+    no_puzzles: "Nessun problema trovato.",
+    find_best: "Tocca a {0}, trova la tessera migliore.",
+    not_best: "Non è la tessera migliore, riprova.",
+    solved: "Risolto!",
+    previous: "Precedente",
+    retry: "Riprova",
+    next: "Successivo",
+    lesson_title: "Lezione {0} di {1}: {2}",
+    not_allowed: "Non questa tessera, scegline una tra quelle attive.",
+    lessons: [
+        Lesson {
+            title: "Scegliere le tessere",
+            text: "I giocatori si alternano. Al tuo turno, posa una delle tessere rimaste. \
+                Ogni tessera si può posare una sola volta. Posa una tessera qualsiasi.",
+            done: "La tessera non c'è più, ora sceglie l'altro giocatore.",
+        },
+        Lesson {
+            title: "La freccia",
+            text: "Una tessera parte dalla freccia ed è girata come lei. \
+                Poi la freccia si sposta alla fine della tessera e gira a sinistra o a destra con lei. \
+                Posa una tessera che gira a destra.",
+            done: "La freccia si è spostata alla fine della tessera e ora punta a destra \
+                rispetto a prima.",
+        },
+        Lesson {
+            title: "Chiudere un anello",
+            text: "Se una tessera incrocia il percorso, chiude un anello. \
+                Ogni punto libero dentro l'anello va al giocatore che ha posato la tessera. \
+                Chiudi l'anello.",
+            done: "I punti dentro sono tuoi. Quando le tessere finiscono, vince chi ha più punti, \
+                in caso di parità il secondo giocatore.",
+        },
+        Lesson {
+            title: "Il bordo",
+            text: "Chi fa finire una tessera fuori dalla tavola perde subito. \
+                Passa sopra le tessere per vedere dove finiscono, quelle fuori sono rosse. \
+                Posa una tessera che resta sulla tavola.",
+            done: "La freccia è ancora sulla tavola. Ora conosci le regole, buon divertimento!",
+        },
+    ],
+
+    menu: "Menu",
+    language: "Lingua",
+    board_size: "Dimensione della tavola",
+    tiles: "Tessere",
+    mode: "Modalità",
+    bot_plays: "Il bot gioca",
+    strength: "Forza",
+    start: "Inizia",
+    board: "Tavola",
+    score_column: "Punteggio",
+    step_column: "Passo",
+    resume: "Riprendi",
+    view: "Guarda",
+    delete: "Elimina",
+
+    server: "Server",
+    name: "Nome",
+    game: "Partita",
+    size: "Dimensione",
+    connect: "Connetti",
+    lobby: "Lobby",
+    create: "Crea",
+    join: "Entra",
+    watch: "Guarda ({0})",
+    connecting: "Connessione...",
+    connected: "Connesso",
+    disconnected: "Disconnesso",
+    connection_failed: "Connessione fallita",
+    invalid_game: "Partita non valida: {0}",
+    invalid_message: "Messaggio del server non valido",
+    games: "{0} partite",
+    waiting: "Partita {0}, in attesa di un avversario",
+    watching: "Guardi la partita {0} al passo {1}",
+    abandoned: "{0} è uscito, il posto si perde tra {1} secondi",
+    returned: "{0} è tornato",
+    versus: "{0} (Gamma) contro {1} (Delta)",
+    resigned: "{0} ha abbandonato",
+    draw_agreed: "Patta concordata",
+    draw_declined: "{0} ha rifiutato la patta",
+};
@@ -0,0 +1,160 @@
+//! Keyboard and gamepad controls, mapped to [`GameMsg`]s.
+
+use super::{GameMsg, Polycentrics};
+use wasm_bindgen::JsCast;
+use yew::web_sys::{Gamepad, GamepadButton, HtmlElement, KeyboardEvent};
+
+/// Ways to move the selection through the tile pad.
+#[derive(Clone, Copy)]
+pub(crate) enum Browse {
+    // the tile before or after, in the order of the pad
+    Left,
+    Right,
+    // the same tile, bending forward or backward
+    Forward,
+    Backward,
+    // the same tile, with another radius
+    Radius(i8),
+}
+
+// indices of the standard gamepad mapping,
+// see https://w3c.github.io/gamepad/#remapping
+const PAD_CONFIRM: usize = 0;
+const PAD_UNDO: usize = 1;
+const PAD_UP: usize = 12;
+const PAD_DOWN: usize = 13;
+const PAD_LEFT: usize = 14;
+const PAD_RIGHT: usize = 15;
+
+/// The [`GameMsg`] of a pressed key, if it has one.
+pub(crate) fn key_msg(event: &KeyboardEvent) -> Option<GameMsg> {
+    let tag = event
+        .target()
+        .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+        .map(|element| element.tag_name());
+    let key = event.key();
+
+    // typing a name or a size is no input to the game,
+    // and enter clicks the focused button
+    let typing = matches!(tag.as_deref(), Some("INPUT" | "SELECT" | "TEXTAREA"))
+        || (key == "Enter" && tag.as_deref() == Some("BUTTON"));
+
+    if typing || event.ctrl_key() || event.alt_key() || event.meta_key() {
+        return None;
+    }
+
+    let msg = match key.as_str() {
+        "ArrowLeft" | "h" => GameMsg::Browse(Browse::Left),
+        "ArrowRight" | "l" => GameMsg::Browse(Browse::Right),
+        "ArrowUp" | "k" => GameMsg::Browse(Browse::Forward),
+        "ArrowDown" | "j" => GameMsg::Browse(Browse::Backward),
+        "1" => GameMsg::Browse(Browse::Radius(1)),
+        "2" => GameMsg::Browse(Browse::Radius(2)),
+        "3" => GameMsg::Browse(Browse::Radius(3)),
+        "Enter" => GameMsg::Confirm,
+        "u" => GameMsg::Undo,
+        _ => return None,
+    };
+
+    // arrow keys would scroll the page
+    event.prevent_default();
+    Some(msg)
+}
+
+/// Pressed buttons of the first connected gamepad, empty if there is none.
+fn gamepad_buttons() -> Vec<bool> {
+    let gamepads = match yew::utils::window().navigator().get_gamepads() {
+        Ok(gamepads) => gamepads,
+        // not supported by the browser
+        Err(_) => return Vec::new(),
+    };
+
+    gamepads
+        .iter()
+        // free slots are `null`
+        .filter_map(|gamepad| gamepad.dyn_into::<Gamepad>().ok())
+        .find(|gamepad| gamepad.connected())
+        .map(|gamepad| {
+            gamepad
+                .buttons()
+                .iter()
+                .map(|button| button.unchecked_into::<GamepadButton>().pressed())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl Polycentrics {
+    /// [`GameMsg`]s of the gamepad buttons pressed since the last poll.
+    // the Gamepad API has no events for buttons, so it is polled every tick
+    pub(crate) fn poll_gamepad(&mut self) -> Vec<GameMsg> {
+        let buttons = gamepad_buttons();
+
+        let pressed = |index: usize| {
+            buttons.get(index).copied().unwrap_or(false)
+                && !self.gamepad.get(index).copied().unwrap_or(false)
+        };
+
+        let msgs = vec![
+            (PAD_LEFT, GameMsg::Browse(Browse::Left)),
+            (PAD_RIGHT, GameMsg::Browse(Browse::Right)),
+            (PAD_UP, GameMsg::Browse(Browse::Forward)),
+            (PAD_DOWN, GameMsg::Browse(Browse::Backward)),
+            (PAD_CONFIRM, GameMsg::Confirm),
+            (PAD_UNDO, GameMsg::Undo),
+        ]
+        .into_iter()
+        .filter(|(index, _)| pressed(*index))
+        .map(|(_, msg)| msg)
+        .collect();
+
+        self.gamepad = buttons;
+        msgs
+    }
+
+    /// Move the selected tile, starting at the first one of the pad.
+    pub(crate) fn browse(&mut self, browse: Browse) {
+        let options = self.board.options();
+        let (left, right) = self.pad_groups();
+        let order: Vec<usize> = left.into_iter().chain(right).collect();
+
+        let current = self
+            .selected
+            .and_then(|selected| order.iter().position(|tile| *tile == selected));
+
+        // the tile like the one at `pos`, but bending `forward` with `radius`
+        let similar = |pos: usize, forward: Option<bool>, radius: Option<i8>| {
+            let curve = &options[order[pos]];
+            let forward = forward.unwrap_or(curve.end.y < 0);
+            let radius = radius.unwrap_or_else(|| curve.end.x.abs());
+
+            order
+                .iter()
+                .position(|tile| {
+                    let end = options[*tile].end;
+                    end.x.signum() == curve.end.x.signum()
+                        && (end.y < 0) == forward
+                        && end.x.abs() == radius
+                })
+                // there might be no such tile, like radius 3 in the 4x2 set
+                .unwrap_or(pos)
+        };
+
+        let pos = match (browse, current) {
+            // the first tile of the radius, if the set has one
+            (Browse::Radius(radius), None) => order
+                .iter()
+                .position(|tile| options[*tile].end.x.abs() == radius)
+                .unwrap_or(0),
+            (_, None) => 0,
+            (Browse::Left, Some(pos)) => pos.saturating_sub(1),
+            (Browse::Right, Some(pos)) => pos + 1,
+            (Browse::Forward, Some(pos)) => similar(pos, Some(true), None),
+            (Browse::Backward, Some(pos)) => similar(pos, Some(false), None),
+            (Browse::Radius(radius), Some(pos)) => similar(pos, None, Some(radius)),
+        };
+
+        self.selected = order.get(pos.min(order.len().saturating_sub(1))).copied();
+        self.preview = self.selected.and_then(|tile| self.preview(tile));
+    }
+}
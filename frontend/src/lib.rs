@@ -0,0 +1,3 @@
+//! Parts of the frontend shared by the app and the Web Worker binary.
+
+pub mod worker;
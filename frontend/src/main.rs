@@ -1,5 +1,41 @@
 use backend::prelude as pyc;
-use yew::prelude::*;
+use polycentrics_frontend::worker::{SearchWorker, WorkerInput, WorkerOutput};
+use serde::{Deserialize, Serialize};
+use yew::{
+    agent::{Bridge, Bridged},
+    prelude::*,
+    services::{
+        interval::IntervalTask, keyboard::KeyListenerHandle, timeout::TimeoutTask,
+        IntervalService, KeyboardService, TimeoutService,
+    },
+};
+
+mod a11y;
+mod analysis;
+mod camera;
+mod clock;
+mod i18n;
+mod input;
+mod menu;
+mod online;
+mod preview;
+mod puzzle;
+mod saves;
+mod status;
+mod theme;
+mod tutorial;
+mod viewer;
+
+use analysis::Analysis;
+use camera::{Camera, CameraMsg};
+use i18n::{fill, Locale};
+use input::Browse;
+use menu::Menu;
+use online::{Online, OnlineMsg};
+use puzzle::{PuzzleMsg, Puzzles};
+use theme::{Theme, ThemeMsg};
+use tutorial::{Tutorial, TutorialMsg};
+use viewer::{Viewer, ViewerMsg};
 
 // use `wee_alloc` as the global allocator
 // TODO: decide if the 4kB are worth the slowness
@@ -11,8 +47,8 @@ fn main() {
     #[cfg(debug_assertions)]
     std::panic::set_hook(Box::new(console_error_panic_hook::hook));
 
-    // mount Polycentrics to body of index.html
-    yew::start_app::<Polycentrics>();
+    // mount the menu, which then mounts Polycentrics, to body of index.html
+    yew::start_app::<Menu>();
 }
 
 /// Holds game and UI state.
@@ -20,22 +56,135 @@ struct Polycentrics {
     // `ComponentLink` is like a reference to a component
     // it can be used to send messages to the component
     link: ComponentLink<Self>,
+    props: GameProps,
     board: pyc::Board,
+    // virtual player asked for hints
+    // its book is used right away, its search runs in the worker
+    bot: pyc::Bot,
+    // virtual player of the mode, `None` if only humans play
+    opponent: Option<pyc::Bot>,
+    // searches off the main thread, so the UI stays responsive
+    worker: Box<dyn Bridge<SearchWorker>>,
+    // the search running in the worker
+    thinking: Option<Thinking>,
+    // id of the last search, answers of older ones are ignored
+    search_id: u32,
+    // the next move of the opponent, delayed so the last one can be seen
+    opponent_task: Option<TimeoutTask>,
+    // tile suggested by the bot, until the next step
+    hint: Option<usize>,
+    // board after setting the hovered tile
+    preview: Option<pyc::Board>,
+    // draw the tile pad as seen from the arrow
+    rotate_pad: bool,
+    // tile chosen with the keyboard or gamepad, set with `Confirm`
+    selected: Option<usize>,
+    // moves since the start, to undo and save them
+    record: pyc::Record,
+    // the game in `localStorage`, a new one on restart
+    save_id: pyc::Millis,
+    // a record viewed move by move, drawn instead of the board
+    viewer: Option<Viewer>,
+    // evaluations of the shown position, `None` while hidden
+    analysis: Option<Analysis>,
+    // `None` unless the mode is `Mode::Puzzle`
+    puzzles: Option<Puzzles>,
+    // `None` unless the mode is `Mode::Tutorial`
+    tutorial: Option<Tutorial>,
+    // what changed with the last move, read by screen readers
+    announcement: String,
+    // colours of the board and the page, kept between visits
+    theme: Theme,
+    // zoom and pan of the board
+    camera: Camera,
+    // connection to a game server, which then holds the board
+    online: Online,
+    // `None` for untimed games
+    clock: Option<pyc::Clock>,
+    // dropping the task stops the ticks
+    _ticker: IntervalTask,
+    // dropping the handle stops listening
+    _keys: KeyListenerHandle,
+    // buttons pressed at the last poll
+    gamepad: Vec<bool>,
 }
 
+// opening book of the default board, generated with `pyc book generate 3 3`
+const OPENING_BOOK: &[u8] = include_bytes!("../assets/opening.pycb");
+
+// steps the bot looks ahead for positions not in the book
+const HINT_DEPTH: u8 = 2;
+
+// time before the opponent moves
+const OPPONENT_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
 /// Attributes a [`Polycentrics`] game can get from Html.
-#[derive(Clone, Properties)]
+#[derive(Clone, Properties, Serialize, Deserialize)]
 struct GameProps {
     // number of points on the board in width and height
     size: u8,
+    tiles: pyc::TileSet,
+    mode: Mode,
+    // id of a saved game to continue, see `saves`
+    #[serde(skip)]
+    resume: Option<pyc::Millis>,
+    // game of a link to view, see `viewer`
+    #[serde(skip)]
+    shared: Option<pyc::Record>,
+    // language of the UI, chosen in the menu
+    #[serde(skip)]
+    locale: Locale,
     // does not need "render only board, not tile pad"
     // because its deduced from `board.state`
-    // TODO: later first player, and pre configuration hash
+    // TODO: later first player, the configuration is shared with `Record::encode`
+}
+
+/// A search running in the [`SearchWorker`].
+struct Thinking {
+    id: u32,
+    // the opponent moves once done, otherwise it is a hint
+    opponent: bool,
+    // progress: depth reached and best tile so far
+    depth: Option<u8>,
+}
+
+/// Who plays a [`Polycentrics`] game.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+enum Mode {
+    // two humans at one screen
+    HotSeat,
+    // a human against a bot searching `depth` steps
+    Bot { player: pyc::Player, depth: u8 },
+    // a bot against itself
+    Watch { depth: u8 },
+    // on a game server, local hot seat until connected
+    Online,
+    // find the best tiles of the bundled puzzles
+    Puzzle,
+    // learn the rules lesson by lesson
+    Tutorial,
+}
+
+impl GameProps {
+    /// The record of a new game, before the first step.
+    fn record(&self) -> pyc::Record {
+        pyc::Record {
+            tiles: self.tiles,
+            ..pyc::Record::with_size(self.size)
+        }
+    }
 }
 
 impl Default for GameProps {
     fn default() -> Self {
-        GameProps { size: 11 }
+        GameProps {
+            size: 11,
+            tiles: pyc::TileSet::default(),
+            mode: Mode::HotSeat,
+            resume: None,
+            shared: None,
+            locale: Locale::default(),
+        }
     }
 }
 
@@ -45,6 +194,46 @@ enum GameMsg {
     RenderBoard,
     // set tile given index
     SetTile(usize),
+    // resign or answer draws, tiles are set with `SetTile`
+    Act(pyc::Action),
+    // suggest a tile
+    Hint,
+    // show where a tile would land, `None` to hide it
+    Preview(Option<usize>),
+    // toggle drawing the tile pad in the arrow direction
+    RotatePad,
+    // show or hide the analysis
+    Analysis,
+    // move the selected tile
+    Browse(Browse),
+    // set the selected tile
+    Confirm,
+    // take back the last move of the user
+    Undo,
+    // change or export the theme
+    Theme(ThemeMsg),
+    // step through a record
+    Viewer(ViewerMsg),
+    // put a link to the game into the address bar
+    Share,
+    // zoom or pan the board
+    Camera(CameraMsg),
+    // choose or retry a puzzle
+    Puzzle(PuzzleMsg),
+    // choose or retry a lesson
+    Tutorial(TutorialMsg),
+    // let the opponent move
+    Opponent,
+    // progress or result of a search
+    Worker(WorkerOutput),
+    // start over with the same props
+    Restart,
+    // play on a game server
+    Online(OnlineMsg),
+    // choose one of `clock::time_controls()`
+    TimeControl(usize),
+    // time passed
+    Tick,
 }
 
 impl Component for Polycentrics {
@@ -56,20 +245,79 @@ impl Component for Polycentrics {
         // initial draw call
         link.send_message(Self::Message::RenderBoard);
 
-        Self {
+        let bot = pyc::Bot {
+            // a broken book only costs speed, so do not fail on it
+            book: pyc::Book::from_bytes(OPENING_BOOK).ok(),
+            ..pyc::Bot::new(HINT_DEPTH)
+        };
+
+        let opponent = match props.mode {
+            Mode::Bot { depth, .. } | Mode::Watch { depth } => Some(pyc::Bot {
+                book: bot.book.clone(),
+                ..pyc::Bot::new(depth)
+            }),
+            Mode::HotSeat | Mode::Online | Mode::Puzzle | Mode::Tutorial => None,
+        };
+
+        let record = props.record();
+        let camera = Camera::board(record.size as usize);
+
+        let mut game = Self {
+            board: record.start(),
+            record,
+            props,
+            bot,
+            opponent,
+            opponent_task: None,
+            worker: SearchWorker::bridge(link.callback(GameMsg::Worker)),
+            thinking: None,
+            search_id: 0,
+            hint: None,
+            preview: None,
+            rotate_pad: true,
+            selected: None,
+            announcement: String::new(),
+            theme: Theme::load(),
+            save_id: clock::now(),
+            viewer: None,
+            analysis: None,
+            puzzles: None,
+            tutorial: None,
+            camera,
+            online: Online::default(),
+            clock: None,
+            _ticker: IntervalService::spawn(clock::TICK, link.callback(|_| GameMsg::Tick)),
+            _keys: KeyboardService::register_key_down(
+                &yew::utils::window(),
+                link.batch_callback(|event: KeyboardEvent| input::key_msg(&event)),
+            ),
+            gamepad: Vec::new(),
             link,
-            board: pyc::Board {
-                arrow: pyc::Arrow {
-                    pos: pyc::Vec2D {
-                        x: props.size as i8 / 2,
-                        y: props.size as i8 / 2,
-                    },
-                    dir: pyc::Direction::North,
-                },
-                points: vec![vec![None; props.size as usize]; props.size as usize],
-                ..Default::default()
-            },
+        };
+
+        if let Some(id) = game.props.resume {
+            game.resume(id);
         }
+        game.viewer = game.props.shared.clone().map(Viewer::new);
+
+        match game.props.mode {
+            Mode::Puzzle => {
+                game.puzzles = Some(Puzzles::load());
+                game.start_puzzle();
+            }
+            Mode::Tutorial => {
+                game.tutorial = Some(Tutorial {
+                    lesson: 0,
+                    done: false,
+                });
+                game.start_lesson();
+            }
+            _ => {}
+        }
+
+        // the bot might begin
+        game.schedule_opponent();
+        game
     }
 
     /// Updates the UI based on an action message [`GameMsg`].
@@ -80,9 +328,25 @@ impl Component for Polycentrics {
                 // update dom
                 true
             }
+            Self::Message::SetTile(tile) if self.online.is_connected() => {
+                // the server steps the board and sends it back,
+                // or refuses if not seated or not on turn
+                self.online.send(&pyc::ClientMsg::Tile {
+                    tile: pyc::TileChoice::Index(tile as u8),
+                });
+                false
+            }
+            Self::Message::SetTile(tile) if self.puzzles.is_some() => {
+                self.solve(tile);
+                true
+            }
+            Self::Message::SetTile(tile) if self.tutorial.is_some() => {
+                self.learn(tile);
+                true
+            }
             Self::Message::SetTile(tile) => {
-                // place tile on board
-                self.board.step(tile);
+                // place tile on board, unless the time ran out
+                self.act(pyc::Action::Tile(tile as u8));
 
                 // re render the board by updating view
                 self.link.send_message(Self::Message::RenderBoard);
@@ -90,30 +354,419 @@ impl Component for Polycentrics {
                 // because view is now already refreshed, do not update again
                 false
             }
+            Self::Message::Act(action) if self.online.is_connected() => {
+                // the server knows the seat, so the player is not sent
+                let msg = match action {
+                    pyc::Action::Tile(tile) => pyc::ClientMsg::Tile {
+                        tile: pyc::TileChoice::Index(tile),
+                    },
+                    pyc::Action::Resign(_) => pyc::ClientMsg::Resign,
+                    pyc::Action::OfferDraw(_) => pyc::ClientMsg::OfferDraw,
+                    pyc::Action::AcceptDraw(_) => pyc::ClientMsg::AcceptDraw,
+                    pyc::Action::DeclineDraw(_) => pyc::ClientMsg::DeclineDraw,
+                };
+
+                self.online.send(&msg);
+                false
+            }
+            Self::Message::Act(action) => {
+                self.act(action);
+                true
+            }
+            // the solution is the best hint
+            Self::Message::Hint if self.puzzles.is_some() => {
+                self.hint = self.puzzles.as_ref().and_then(Puzzles::expected);
+                true
+            }
+            Self::Message::Hint if self.tutorial.is_some() => {
+                self.hint = self.lesson_hint();
+                true
+            }
+            Self::Message::Hint => {
+                match self.bot.book.as_ref().and_then(|book| book.best(&self.board)) {
+                    Some(tile) => self.hint = Some(tile),
+                    None => self.think(false, self.bot.search.depth),
+                }
+
+                true
+            }
+            Self::Message::Preview(tile) => {
+                self.preview = tile.and_then(|tile| self.preview(tile));
+                true
+            }
+            Self::Message::RotatePad => {
+                self.rotate_pad = !self.rotate_pad;
+                true
+            }
+            Self::Message::Analysis => {
+                self.analysis = match self.analysis {
+                    Some(_) => None,
+                    None => Some(Analysis::default()),
+                };
+                true
+            }
+            // the arrow keys step through the viewed moves
+            Self::Message::Browse(Browse::Left) if self.viewer.is_some() => {
+                self.update_viewer(ViewerMsg::Back)
+            }
+            Self::Message::Browse(Browse::Right) if self.viewer.is_some() => {
+                self.update_viewer(ViewerMsg::Forward)
+            }
+            Self::Message::Browse(browse) => {
+                self.browse(browse);
+                true
+            }
+            Self::Message::Confirm => match self.selected {
+                Some(tile) if self.controls(self.board.active) => self.update(GameMsg::SetTile(tile)),
+                _ => false,
+            },
+            Self::Message::Undo => {
+                self.undo();
+                true
+            }
+            Self::Message::Theme(msg) => self.update_theme(msg),
+            Self::Message::Viewer(msg) => self.update_viewer(msg),
+            Self::Message::Share => {
+                self.share();
+                true
+            }
+            Self::Message::Camera(msg) => self.update_camera(msg),
+            Self::Message::Puzzle(msg) => self.update_puzzle(msg),
+            Self::Message::Tutorial(msg) => self.update_tutorial(msg),
+            Self::Message::Opponent => {
+                self.opponent_task = None;
+
+                match self.board.draw_offer {
+                    // the bot only takes a draw if it is behind
+                    Some(offer) if !self.plays_bot(offer) => {
+                        let player = offer.opponent();
+                        let (own, other) = match player {
+                            pyc::Player::Gamma => (self.board.score.gamma, self.board.score.delta),
+                            pyc::Player::Delta => (self.board.score.delta, self.board.score.gamma),
+                        };
+
+                        self.act(if own < other {
+                            pyc::Action::AcceptDraw(player)
+                        } else {
+                            pyc::Action::DeclineDraw(player)
+                        });
+                    }
+                    _ => {
+                        if let Some(bot) = &self.opponent {
+                            let depth = bot.search.depth;
+
+                            match bot.book.as_ref().and_then(|book| book.best(&self.board)) {
+                                Some(tile) => self.act(pyc::Action::Tile(tile as u8)),
+                                None => self.think(true, depth),
+                            }
+                        }
+                    }
+                }
+
+                true
+            }
+            Self::Message::Worker(output) => {
+                let id = match &output {
+                    WorkerOutput::Progress { id, .. } | WorkerOutput::Done { id, .. } => *id,
+                };
+
+                // an answer to a cancelled search
+                let thinking = match self.thinking.as_mut() {
+                    Some(thinking) if thinking.id == id => thinking,
+                    _ => return false,
+                };
+
+                match output {
+                    WorkerOutput::Progress { depth, tile, .. } => {
+                        thinking.depth = Some(depth);
+
+                        // the best tile so far is already a good hint
+                        if !thinking.opponent {
+                            self.hint = Some(tile);
+                        }
+                    }
+                    WorkerOutput::Done { tile, .. } => {
+                        let opponent = thinking.opponent;
+                        self.thinking = None;
+
+                        match (opponent, tile) {
+                            (true, Some(tile)) => self.act(pyc::Action::Tile(tile as u8)),
+                            (false, tile) => self.hint = tile,
+                            _ => {}
+                        }
+                    }
+                }
+
+                true
+            }
+            Self::Message::Restart if self.puzzles.is_some() => self.update_puzzle(PuzzleMsg::Retry),
+            Self::Message::Restart if self.tutorial.is_some() => {
+                self.update_tutorial(TutorialMsg::Retry)
+            }
+            Self::Message::Restart => {
+                self.cancel_thinking();
+                self.opponent_task = None;
+
+                // the finished game stays saved
+                self.record = self.props.record();
+                self.board = self.record.start();
+                self.save_id = clock::now();
+                self.hint = None;
+                self.preview = None;
+                self.selected = None;
+                self.announcement.clear();
+                // same time control, full time
+                self.clock = self.clock.as_ref().map(|clock| pyc::Clock::new(clock.control));
+
+                // the bot might begin
+                self.schedule_opponent();
+                true
+            }
+            Self::Message::Online(msg) => self.update_online(msg),
+            Self::Message::TimeControl(index) => {
+                let control = clock::time_controls(self.texts())
+                    .get(index)
+                    .and_then(|(_, control)| *control);
+                self.clock = control.map(pyc::Clock::new);
+                true
+            }
+            Self::Message::Tick => {
+                let msgs = self.poll_gamepad();
+                if !msgs.is_empty() {
+                    self.link.send_message_batch(msgs);
+                }
+
+                match self.clock.as_mut() {
+                    Some(clock) => {
+                        // online, the server decides when the time is up
+                        if !self.online.is_connected() && clock.check(&mut self.board, clock::now()) {
+                            self.announcement = a11y::result_label(self.texts(), &self.board.state).unwrap_or_default();
+                            // the record alone does not know the game is lost
+                            self.save();
+                        }
+                        true
+                    }
+                    None => false,
+                }
+            }
         }
     }
 
-    /// Default set to false, as game properties can not change while playing.
-    // TODO: reference to `self` is not needed, so `_`?
-    fn change(&mut self, _: Self::Properties) -> ShouldRender {
+    /// Only the language can change while playing, the other properties start a new game.
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
         // should only return "true" if new properties are different to
         // previously received properties.
-        false
+        if props.locale != self.props.locale {
+            self.props.locale = props.locale;
+            true
+        } else {
+            false
+        }
     }
 
-    // # rendered will do nothing by default, so no implementation
+    /// Analyse a new position once it is drawn, so the board does not wait for the search.
+    fn rendered(&mut self, _first_render: bool) {
+        if self.analyse() {
+            self.link.send_message(Self::Message::RenderBoard);
+        }
+    }
 
     /// Render [`Html`] view of [`Polycentrics`].
     fn view(&self) -> Html {
+        let (theme, style) = self.theme_attributes();
+
         html! {
-            <div class="polycentrics">
-                { self.board_view() }
-                { self.tile_pad_view() }
+            <div class=classes!("polycentrics", theme) style=style>
+                // read out on every change, but not shown
+                <div class="visually-hidden" role="status" aria-live="polite">{ &self.announcement }</div>
+                { self.status_view() }
+                { self.clock_view() }
+                <div class="board-container">
+                    { self.board_view() }
+                    { self.game_over_view() }
+                </div>
+                { self.camera_view() }
+                {
+                    // nothing to play while viewing
+                    if self.viewer.is_some() {
+                        self.viewer_view()
+                    } else {
+                        html! {
+                            <>
+                                { self.tile_pad_view() }
+                                {
+                                    if self.puzzles.is_some() {
+                                        self.puzzle_view()
+                                    } else if self.tutorial.is_some() {
+                                        self.tutorial_view()
+                                    } else {
+                                        self.actions_view()
+                                    }
+                                }
+                            </>
+                        }
+                    }
+                }
+                { self.analysis_view() }
+                {
+                    match self.props.mode {
+                        Mode::Online => self.online_view(),
+                        _ => html! {},
+                    }
+                }
+                { self.theme_view() }
             </div>
         }
     }
 }
 
+// # Players
+
+impl Polycentrics {
+    /// Do an [`pyc::Action`] on the local board and let the opponent answer.
+    // the buttons only offer valid actions, so errors are ignored
+    fn act(&mut self, action: pyc::Action) {
+        // the search and selection belong to the previous position
+        self.cancel_thinking();
+        self.preview = None;
+        self.selected = None;
+
+        let before = self.board.clone();
+        let done = match self.clock.as_mut() {
+            Some(clock) => clock.act(&mut self.board, action, clock::now()),
+            None => self.board.act(action),
+        };
+
+        if done.is_ok() {
+            self.record.moves.push(action);
+            self.save();
+        }
+        self.announce(&before);
+
+        // the hint belongs to the previous position
+        self.hint = None;
+        self.schedule_opponent();
+    }
+
+    /// There is a tile of the user to take back.
+    fn can_undo(&self) -> bool {
+        // the bot also answered since, and nothing is left to undo if it plays alone
+        let user = [pyc::Player::Gamma, pyc::Player::Delta]
+            .iter()
+            .any(|player| self.controls(*player));
+
+        // puzzles and lessons are retried instead
+        user && !self.online.is_connected()
+            && self.clock.is_none()
+            && self.puzzles.is_none()
+            && self.tutorial.is_none()
+            && self.record.moves.iter().any(|action| matches!(action, pyc::Action::Tile(_)))
+    }
+
+    /// Take back the moves since the last tile the user set, by replaying the rest.
+    // not online or with a clock, because the time can not be taken back
+    fn undo(&mut self) {
+        if !self.can_undo() {
+            return;
+        }
+
+        self.cancel_thinking();
+        self.opponent_task = None;
+
+        while let Some(action) = self.record.moves.pop() {
+            // the moves before the undone ones were played already
+            self.board = self.record.replay().unwrap();
+
+            if matches!(action, pyc::Action::Tile(_)) && self.controls(self.board.active) {
+                break;
+            }
+        }
+
+        self.hint = None;
+        self.preview = None;
+        self.selected = None;
+        let texts = self.texts();
+        self.announcement = fill(
+            texts.took_back,
+            &[
+                &online::player_name(texts, self.board.active),
+                &self.board.score.gamma,
+                &self.board.score.delta,
+            ],
+        );
+        self.save();
+        self.schedule_opponent();
+    }
+
+    /// Search the best tile of the board in the worker, up to `depth`.
+    /// The `opponent` moves once done, otherwise it is a hint.
+    fn think(&mut self, opponent: bool, depth: u8) {
+        self.cancel_thinking();
+        self.search_id += 1;
+
+        self.worker.send(WorkerInput::Search {
+            id: self.search_id,
+            board: self.board.clone(),
+            depth,
+        });
+
+        self.thinking = Some(Thinking {
+            id: self.search_id,
+            opponent,
+            depth: None,
+        });
+    }
+
+    /// Stop the search in the worker, if any.
+    fn cancel_thinking(&mut self) {
+        if let Some(thinking) = self.thinking.take() {
+            self.worker.send(WorkerInput::Cancel { id: thinking.id });
+        }
+    }
+
+    /// The bot of the mode plays `player`.
+    fn plays_bot(&self, player: pyc::Player) -> bool {
+        match self.props.mode {
+            Mode::Bot { player: bot, .. } => bot == player,
+            Mode::Watch { .. } => true,
+            Mode::HotSeat | Mode::Online | Mode::Puzzle | Mode::Tutorial => false,
+        }
+    }
+
+    /// The user may act for `player`.
+    fn controls(&self, player: pyc::Player) -> bool {
+        if self.viewer.is_some() {
+            false
+        } else if self.online.is_connected() {
+            self.online.player() == Some(player)
+        } else if let Some(puzzles) = &self.puzzles {
+            // the answers are set right away, so the solver is always on turn
+            !puzzles.solved()
+        } else if let Some(tutorial) = &self.tutorial {
+            // one tile per lesson, for either player
+            !tutorial.done
+        } else {
+            !self.plays_bot(player)
+        }
+    }
+
+    /// Let the opponent move after a delay, if it is on turn or has to answer a draw offer.
+    fn schedule_opponent(&mut self) {
+        if self.opponent.is_none() || !matches!(self.board.state, pyc::State::Pending) {
+            return;
+        }
+
+        let answer = matches!(self.board.draw_offer, Some(offer) if !self.plays_bot(offer));
+
+        if answer || self.plays_bot(self.board.active) {
+            self.opponent_task = Some(TimeoutService::spawn(
+                OPPONENT_DELAY,
+                self.link.callback(|_| GameMsg::Opponent),
+            ));
+        }
+    }
+}
+
 // # HTML views
 
 impl Polycentrics {
@@ -122,24 +775,34 @@ impl Polycentrics {
     // if you want non interactive, set `Board.state`
     // to `State::Draw | State::Victory(_)`
     fn board_view(&self) -> Html {
+        let [down, moved, up, cancel] = self.camera_listeners();
+
         html! {
             <svg
                 class="board"
                 xmlns="http://www.w3.org/2000/svg"
+                role="group"
+                aria-label=self.board_label()
 
-                // could be optimized to only render to string once,
-                // but needs to be cloned anyway
-                width=self.board.points.len().to_string()
-                height=self.board.points.len().to_string()
+                // sized by css, the `viewBox` zooms and pans,
+                // so the geometry is drawn at the resolution of the screen
+                viewBox=self.camera.view_box()
+                shape-rendering="geometricPrecision"
 
-                // TODO: maybe 0.5 looks better?
-                viewBox=format!("-1 -1 {0} {0}", self.board.points.len() + 1)
+                onpointerdown=down
+                onpointermove=moved
+                onpointerup=up
+                onpointercancel=cancel
+                onwheel=self.camera_wheel()
             >
                 // reversed stack draw
                 // { self.midpoint_svg() } // DEBUG VIEW!
                 // { self.intersections_svg() } // DEBUG VIEW!
+                { self.captures_svg() }
                 { self.path_svg() }
                 { self.points_svg() }
+                { self.analysis_svg() }
+                { self.preview_svg() }
                 { self.arrow_svg() }
             </svg>
         }
@@ -149,37 +812,206 @@ impl Polycentrics {
     // not a component because not sure how yew handles components
     // communication for tile click, also destroy on update
     fn tile_pad_view(&self) -> Html {
+        let options = self.board.options();
+        let (left, right) = self.pad_groups();
+        let texts = self.texts();
+
         html! {
-            <div class="tile-pad">{
-                // iterate through all tile options
-                self.board
-                    .options()
-                    .iter()
-                    // include the index as argument for `Board.step(i)`
-                    .enumerate()
-                    .map(|(i, curve)| {
+            <div class="tile-pad">
+                <label class="tile-pad-rotate">
+                    <input
+                        type="checkbox"
+                        checked=self.rotate_pad
+                        onclick=self.link.callback(|_| GameMsg::RotatePad)
+                    />
+                    { " " }{ texts.rotate_pad }
+                </label>
+                <div class="tile-pad-group">
+                    <span class="tile-pad-label">{ texts.turn_left }</span>
+                    { left.into_iter().map(|i| self.tile_button(i, &options[i])).collect::<Html>() }
+                </div>
+                <div class="tile-pad-group">
+                    <span class="tile-pad-label">{ texts.turn_right }</span>
+                    { right.into_iter().map(|i| self.tile_button(i, &options[i])).collect::<Html>() }
+                </div>
+            </div>
+        }
+    }
+
+    /// Indices of the tiles turning the arrow left, outer first, and right, inner first,
+    /// so they lie on the pad like on the board.
+    // the index is the argument for `Board.step(i)`
+    fn pad_groups(&self) -> (Vec<usize>, Vec<usize>) {
+        let options = self.board.options();
+        let group = |side: i8| {
+            let mut group: Vec<usize> = (0..options.len())
+                .filter(|i| options[*i].end.x.signum() == side)
+                .collect();
+            // ascending `x` is outer first on the left, inner first on the right
+            group.sort_by_key(|i| options[*i].end.x);
+            group
+        };
+
+        (group(-1), group(1))
+    }
+
+    /// [`Html`] button to set a tile, drawn in the arrow direction if `rotate_pad`.
+    fn tile_button(&self, i: usize, curve: &pyc::Curve) -> Html {
+        // the same rotation as `Board.step()`, the tile starts at the origin
+        let dir = if self.rotate_pad {
+            self.board.arrow.dir
+        } else {
+            pyc::Direction::North
+        };
+        let mid = curve.mid.rotate(dir);
+        let end = curve.end.rotate(dir);
+
+        html! {
+            // TODO: replace with SVG view
+            <button
+                class=classes!(
+                    "tile-button",
+                    (self.hint == Some(i)).then_some("tile-hint"),
+                    (self.selected == Some(i)).then_some("tile-selected"),
+                )
+                // not on turn of the bot, or the opponent online
+                disabled={ !self.controls(self.board.active) || !self.allowed(i) }
+                onclick=self.link.callback(move |_| GameMsg::SetTile(i))
+                // preview on hover and keyboard focus
+                onmouseenter=self.link.callback(move |_| GameMsg::Preview(Some(i)))
+                onmouseleave=self.link.callback(|_| GameMsg::Preview(None))
+                onfocus=self.link.callback(move |_| GameMsg::Preview(Some(i)))
+                onblur=self.link.callback(|_| GameMsg::Preview(None))
+                // the same for every direction of the arrow
+                aria-label=a11y::tile_label(self.texts(), curve)
+            >
+                <svg
+                    class="tile"
+                    xmlns="http://www.w3.org/2000/svg"
+                    aria-hidden="true"
+
+                    width=3
+                    height=3
+                    
+                    viewBox=format!("{} {} {} {}", if end.x > 0 { -0.2 } else { -3.2 }, if end.y > 0 { -0.2 } else { -3.2 }, 3.4, 3.4)
+                >
+                    <path
+                        class="curve"
+                        d=format!("M 0 0 Q {} {} {} {}", mid.x, mid.y, end.x, end.y)
+                    />
+                </svg>
+            </button>
+        }
+    }
+
+    /// [`Html`] view of the actions besides setting tiles.
+    fn actions_view(&self) -> Html {
+        let over = !matches!(self.board.state, pyc::State::Pending);
+
+        // whoever is on turn, or else the other player the user controls
+        let active = self.board.active;
+        let player = [active, active.opponent()]
+            .iter()
+            .copied()
+            .find(|player| self.controls(*player));
+        let texts = self.texts();
+
+        html! {
+            <div class="actions">
+                <button
+                    class="action-button"
+                    // nothing to suggest if the game is over
+                    disabled={ self.board.options().is_empty() || self.thinking.is_some() }
+                    onclick=self.link.callback(|_| GameMsg::Hint)
+                >{ texts.hint }</button>
+                <button
+                    class="action-button"
+                    disabled=!self.can_undo()
+                    onclick=self.link.callback(|_| GameMsg::Undo)
+                >{ texts.undo }</button>
+                <button
+                    class="action-button"
+                    // the server holds the moves of online games
+                    disabled=self.online.is_connected()
+                    onclick=self.link.callback(|_| GameMsg::Share)
+                >{ texts.share }</button>
+                <button
+                    class="action-button"
+                    disabled={ self.online.is_connected() || self.record.moves.is_empty() }
+                    onclick=self.link.callback(|_| GameMsg::Viewer(ViewerMsg::Open))
+                >{ texts.review }</button>
+                {
+                    match &self.thinking {
+                        Some(thinking) => html! {
+                            <span class="thinking">{
+                                match thinking.depth {
+                                    Some(depth) => fill(texts.thinking_ahead, &[&(depth + 1)]),
+                                    None => texts.thinking.to_string(),
+                                }
+                            }</span>
+                        },
+                        None => html! {},
+                    }
+                }
+                {
+                    match player {
+                        Some(player) => html! {
+                            <>
+                                <button
+                                    class="action-button"
+                                    disabled=over
+                                    onclick=self.link.callback(move |_| GameMsg::Act(pyc::Action::Resign(player)))
+                                >{ texts.resign }</button>
+                                <button
+                                    class="action-button"
+                                    disabled={ over || self.board.draw_offer.is_some() }
+                                    onclick=self.link.callback(move |_| GameMsg::Act(pyc::Action::OfferDraw(player)))
+                                >{ texts.offer_draw }</button>
+                            </>
+                        },
+                        None => html! {},
+                    }
+                }
+                { self.time_control_view() }
+                { self.draw_offer_view() }
+            </div>
+        }
+    }
+
+    /// [`Html`] view of a pending draw offer, with answers for the opponent.
+    fn draw_offer_view(&self) -> Html {
+        let offer = match self.board.draw_offer {
+            Some(offer) => offer,
+            None => return html! {},
+        };
+
+        // the answer always comes from the opponent of the offer
+        let player = offer.opponent();
+        let answer = self.controls(player);
+        let texts = self.texts();
+
+        html! {
+            <div class="draw-offer">
+                <span>{ fill(texts.offers_draw, &[&online::player_name(texts, offer)]) }</span>
+                {
+                    if answer {
                         html! {
-                            // TODO: replace with SVG view
-                            <button class="tile-button" onclick=self.link.callback(move |_| GameMsg::SetTile(i))>
-                                <svg
-                                    class="tile"
-                                    xmlns="http://www.w3.org/2000/svg"
-
-                                    width=3
-                                    height=3
-                                    
-                                    viewBox=format!("{} {} {} {}", if curve.end.x > 0 { -0.2 } else { -3.2 }, if curve.end.y > 0 { -0.2 } else { -3.2 }, 3.4, 3.4)
-                                >
-                                    <path
-                                        class="curve"
-                                        d=format!("M 0 0 Q {} {} {} {}", curve.mid.x, curve.mid.y, curve.end.x, curve.end.y)
-                                    />
-                                </svg>
-                            </button>
+                            <>
+                                <button
+                                    class="action-button"
+                                    onclick=self.link.callback(move |_| GameMsg::Act(pyc::Action::AcceptDraw(player)))
+                                >{ texts.accept }</button>
+                                <button
+                                    class="action-button"
+                                    onclick=self.link.callback(move |_| GameMsg::Act(pyc::Action::DeclineDraw(player)))
+                                >{ texts.decline }</button>
+                            </>
                         }
-                    })
-                    .collect::<Html>()
-            }</div>
+                    } else {
+                        html! {}
+                    }
+                }
+            </div>
         }
     }
 }
@@ -191,11 +1023,11 @@ impl Polycentrics {
     // this cant be static as points can change color
     fn points_svg(&self) -> Html {
         // iter through all the points
-        self.board
+        self.shown()
             .points
             .iter()
             .enumerate()
-            .map(|(j, points)| {
+            .flat_map(|(j, points)| {
                 points.iter().enumerate().map(move |(i, point)| {
                     html! {
                         <circle class=match point {
@@ -209,6 +1041,8 @@ impl Polycentrics {
                             }
                             // set position to the indices
                             cx=i.to_string() cy=j.to_string()
+                            role="img"
+                            aria-label=a11y::point_label(self.texts(), i, j, *point)
                             // the [geometric property `r` of svg 2](https://svgwg.org/svg2-draft/geometry.html#R) is not currently (88) supported in firefox
                             // TODO: remove when supported, also at `midpoint_svg()`
                             r="0.1"
@@ -216,22 +1050,55 @@ impl Polycentrics {
                     }
                 })
             })
-            .flatten()
+            .collect()
+    }
+
+    /// Render the captured regions of [`Board`] to SVG.
+    fn captures_svg(&self) -> Html {
+        self.shown()
+            .captures
+            .iter()
+            .map(|capture| {
+                let class = match capture.player {
+                    pyc::Player::Gamma => "capture-gamma",
+                    pyc::Player::Delta => "capture-delta",
+                };
+                let texts = self.texts();
+                let player = online::player_name(texts, capture.player);
+
+                html! {
+                    <polygon
+                        class=classes!("capture", class, self.is_latest(capture.step).then_some("capture-latest"))
+                        points=capture
+                            .poly
+                            .iter()
+                            .map(|vertex| format!("{},{}", vertex.x, vertex.y))
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    >
+                        // shown on hover
+                        <title>{ fill(texts.capture_title, &[&capture.step, &player, &capture.points]) }</title>
+                    </polygon>
+                }
+            })
             .collect()
     }
 
     /// Render the path of [`Board`] to SVG.
     fn path_svg(&self) -> Html {
         // iter trough all the path elements
-        self.board
+        self.shown()
             .path
             .iter()
-            .map(|curve| {
+            .enumerate()
+            .map(|(i, curve)| {
                 // let circ = curve.radius as f64 * TAU;
+                // steps count from one, like `Capture::step`
+                let step = i as u8 + 1;
 
                 html! {
                     <path
-                        class="curve"
+                        class=classes!("curve", self.is_latest(step).then_some("curve-latest"))
                         d=format!("M {} {} Q {} {} {} {}", curve.start.x, curve.start.y, curve.mid.x, curve.mid.y, curve.end.x, curve.end.y)
                     />
                     
@@ -259,7 +1126,7 @@ impl Polycentrics {
     /* /// Render the midpoints of the [`Curve`] to SVG.
     // DEBUG VIEW!
     fn midpoint_svg(&self) -> Html {
-        self.board
+        self.shown()
             .path
             .iter()
             .map(|curve| {
@@ -277,24 +1144,16 @@ impl Polycentrics {
     /// Render the arrow of [`Board`] to SVG.
     fn arrow_svg(&self) -> Html {
         // use std::f32::consts::TAU;
+        let arrow = &self.shown().arrow;
+        let (x2, y2) = arrow_tip(arrow);
 
         html! {
             // TODO: something fancier than line
             <line
                 class="arrow"
                 // set position
-                x1=self.board.arrow.pos.x.to_string() y1=self.board.arrow.pos.y.to_string()
-                // the arrow statically points to the right,
-                x2={ self.board.arrow.pos.x as f32 + (match self.board.arrow.dir {
-                    pyc::Direction::North | pyc::Direction::South => 0,
-                    pyc::Direction::West => -1,
-                    pyc::Direction::East => 1,
-                } as f32) / 2.0 }.to_string()
-                y2={ self.board.arrow.pos.y as f32 + (match self.board.arrow.dir {
-                    pyc::Direction::West | pyc::Direction::East => 0,
-                    pyc::Direction::North => -1,
-                    pyc::Direction::South => 1,
-                } as f32) / 2.0 }.to_string()
+                x1=arrow.pos.x.to_string() y1=arrow.pos.y.to_string()
+                x2=x2.to_string() y2=y2.to_string()
                 /* // and than gets rotated
                 transform=format!("rotate({} {} {})", 360.0 * self.board.arrow.angle.0 / TAU, &self.board.arrow.position.x, &self.board.arrow.position.y) */
             />
@@ -303,7 +1162,7 @@ impl Polycentrics {
 
     /* /// Render intersections points with latest tile.
     fn intersections_svg(&self) -> Html {
-        self.board
+        self.shown()
             .intersections()
             .iter()
             .map(|(point, _, _, _)| {
@@ -317,4 +1176,20 @@ impl Polycentrics {
             })
             .collect()
     } */
-}
\ No newline at end of file
+}
+
+/// End of the arrow line, half a unit in its direction.
+fn arrow_tip(arrow: &pyc::Arrow) -> (f32, f32) {
+    // the arrow statically points to the right,
+    let (dx, dy) = match arrow.dir {
+        pyc::Direction::North => (0, -1),
+        pyc::Direction::East => (1, 0),
+        pyc::Direction::South => (0, 1),
+        pyc::Direction::West => (-1, 0),
+    };
+
+    (
+        arrow.pos.x as f32 + dx as f32 / 2.0,
+        arrow.pos.y as f32 + dy as f32 / 2.0,
+    )
+}
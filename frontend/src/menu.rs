@@ -0,0 +1,411 @@
+use super::{
+    i18n::{fill, Locale, Texts},
+    saves::{self, SavedGame},
+    viewer, GameProps, Mode, Polycentrics,
+};
+use backend::prelude as pyc;
+use yew::prelude::*;
+
+/// Start screen to configure a [`Polycentrics`] game, and the game once started.
+pub(crate) struct Menu {
+    link: ComponentLink<Self>,
+    // the configuration of the next game
+    props: GameProps,
+    // the game is shown instead of the menu
+    playing: bool,
+    // with their last board, to show the score
+    saves: Vec<(SavedGame, pyc::Board)>,
+}
+
+/// Messages of the [`Menu`].
+pub(crate) enum MenuMsg {
+    Size(String),
+    // index into `TileSet::ALL`
+    Tiles(usize),
+    // index into `Texts::modes`
+    Mode(usize),
+    BotPlayer(pyc::Player),
+    // index into `STRENGTHS`
+    Strength(usize),
+    Start,
+    Back,
+    // continue or forget a saved game by id
+    Resume(pyc::Millis),
+    Delete(pyc::Millis),
+    // index into `Locale::ALL`
+    Locale(usize),
+}
+
+// search depths of the bot, higher is stronger and slower,
+// named by `Texts::strengths`
+const STRENGTHS: [u8; 3] = [1, 2, 3];
+
+/// Name of a [`pyc::TileSet`] for menus and the lobby.
+pub(crate) fn tile_set_name(texts: &Texts, tiles: pyc::TileSet) -> String {
+    match tiles {
+        pyc::TileSet::Convex4x3 => "4x3".to_string(),
+        pyc::TileSet::Convex4x2 => "4x2".to_string(),
+        pyc::TileSet::Double4x3 => fill(texts.twice, &[&"4x3"]),
+    }
+}
+
+impl Menu {
+    /// The [`Mode`] of an index into `Texts::modes`, keeping the bot settings.
+    fn mode(&self, index: usize) -> Mode {
+        let (player, depth) = match self.props.mode {
+            Mode::Bot { player, depth } => (player, depth),
+            Mode::Watch { depth } => (pyc::Player::Delta, depth),
+            _ => (pyc::Player::Delta, STRENGTHS[1]),
+        };
+
+        match index {
+            1 => Mode::Bot { player, depth },
+            2 => Mode::Watch { depth },
+            3 => Mode::Online,
+            4 => Mode::Puzzle,
+            5 => Mode::Tutorial,
+            _ => Mode::HotSeat,
+        }
+    }
+
+    /// Load the saved games, with their boards.
+    fn load_saves(&mut self) {
+        self.saves = saves::load()
+            .into_iter()
+            .filter_map(|game| {
+                let board = game.board()?;
+                Some((game, board))
+            })
+            .collect();
+    }
+}
+
+/// Index of a mode in `Texts::modes`.
+fn mode_index(mode: Mode) -> usize {
+    match mode {
+        Mode::HotSeat => 0,
+        Mode::Bot { .. } => 1,
+        Mode::Watch { .. } => 2,
+        Mode::Online => 3,
+        Mode::Puzzle => 4,
+        Mode::Tutorial => 5,
+    }
+}
+
+impl Component for Menu {
+    type Message = MenuMsg;
+    type Properties = ();
+
+    fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let locale = Locale::load();
+        locale.apply();
+
+        let mut menu = Self {
+            link,
+            props: GameProps {
+                locale,
+                ..GameProps::default()
+            },
+            playing: false,
+            saves: Vec::new(),
+        };
+        menu.load_saves();
+
+        // view the game of a link, or continue where the last visit ended
+        if let Some(record) = viewer::shared_record() {
+            menu.props = GameProps {
+                size: record.size,
+                tiles: record.tiles,
+                shared: Some(record),
+                locale,
+                ..GameProps::default()
+            };
+            menu.playing = true;
+        } else if let Some((game, _)) = menu
+            .saves
+            .first()
+            .filter(|(_, board)| matches!(board.state, pyc::State::Pending))
+        {
+            let id = game.id;
+            menu.update(MenuMsg::Resume(id));
+        }
+
+        menu
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            // invalid sizes are kept, so typing is not interrupted
+            MenuMsg::Size(size) => match size.trim().parse() {
+                Ok(size) if pyc::SIZES.contains(&size) => self.props.size = size,
+                _ => return false,
+            },
+            MenuMsg::Tiles(index) => {
+                if let Some(tiles) = pyc::TileSet::ALL.get(index) {
+                    self.props.tiles = *tiles;
+                }
+            }
+            MenuMsg::Mode(index) => self.props.mode = self.mode(index),
+            MenuMsg::BotPlayer(bot) => {
+                if let Mode::Bot { player, .. } = &mut self.props.mode {
+                    *player = bot;
+                }
+            }
+            MenuMsg::Strength(index) => {
+                let strength = STRENGTHS.get(index).copied();
+
+                match (&mut self.props.mode, strength) {
+                    (Mode::Bot { depth, .. }, Some(strength))
+                    | (Mode::Watch { depth }, Some(strength)) => *depth = strength,
+                    _ => {}
+                }
+            }
+            MenuMsg::Start => {
+                self.props.resume = None;
+                self.props.shared = None;
+                self.playing = true;
+            }
+            MenuMsg::Back => {
+                // the game saved moves meanwhile
+                self.load_saves();
+                self.playing = false;
+            }
+            MenuMsg::Resume(id) => {
+                if let Some((game, board)) = self.saves.iter().find(|(game, _)| game.id == id) {
+                    // finished games are only viewed
+                    let pending = matches!(board.state, pyc::State::Pending);
+
+                    self.props = GameProps {
+                        resume: Some(id).filter(|_| pending),
+                        shared: Some(game.record.clone()).filter(|_| !pending),
+                        // saves do not keep the language
+                        locale: self.props.locale,
+                        ..game.props.clone()
+                    };
+                    self.playing = true;
+                }
+            }
+            MenuMsg::Delete(id) => {
+                saves::delete(id);
+                self.saves.retain(|(game, _)| game.id != id);
+            }
+            MenuMsg::Locale(index) => {
+                if let Some(locale) = Locale::ALL.get(index) {
+                    self.props.locale = *locale;
+                    locale.save();
+                    locale.apply();
+                }
+            }
+        }
+
+        true
+    }
+
+    fn change(&mut self, _: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn view(&self) -> Html {
+        let texts = self.props.locale.texts();
+
+        if self.playing {
+            return html! {
+                <>
+                    <button
+                        class="action-button"
+                        onclick=self.link.callback(|_| MenuMsg::Back)
+                    >{ texts.menu }</button>
+                    { self.locale_view() }
+                    <Polycentrics with self.props.clone() />
+                </>
+            };
+        }
+
+        html! {
+            <div class="menu">
+                { self.locale_view() }
+                { self.board_view() }
+                <label>
+                    { texts.mode }{ " " }
+                    <select onchange=self.link.callback(|data| MenuMsg::Mode(selected(data)))>{
+                        texts
+                            .modes
+                            .iter()
+                            .enumerate()
+                            .map(|(i, name)| html! {
+                                <option selected={ i == mode_index(self.props.mode) }>{ name }</option>
+                            })
+                            .collect::<Html>()
+                    }</select>
+                </label>
+                { self.bot_view() }
+                <button
+                    class="action-button"
+                    onclick=self.link.callback(|_| MenuMsg::Start)
+                >{ texts.start }</button>
+                { self.saves_view() }
+            </div>
+        }
+    }
+}
+
+impl Menu {
+    /// [`Html`] select of the language, in the menu and while playing.
+    fn locale_view(&self) -> Html {
+        html! {
+            <label class="locale">
+                { self.props.locale.texts().language }{ " " }
+                <select onchange=self.link.callback(|data| MenuMsg::Locale(selected(data)))>{
+                    Locale::ALL
+                        .iter()
+                        .map(|locale| html! {
+                            // each name in its own language
+                            <option lang=locale.code() selected={ *locale == self.props.locale }>{ locale.name() }</option>
+                        })
+                        .collect::<Html>()
+                }</select>
+            </label>
+        }
+    }
+
+    /// [`Html`] table of the saved games.
+    fn saves_view(&self) -> Html {
+        if self.saves.is_empty() {
+            return html! {};
+        }
+
+        let texts = self.props.locale.texts();
+
+        html! {
+            <table class="saves">
+                <tr>
+                    <th>{ texts.board }</th>
+                    <th>{ texts.mode }</th>
+                    <th>{ texts.step_column }</th>
+                    <th>{ texts.score_column }</th>
+                    <th></th>
+                </tr>
+                {
+                    self.saves
+                        .iter()
+                        .map(|(game, board)| {
+                            let id = game.id;
+
+                            html! {
+                                <tr>
+                                    <td>{ format!("{0}x{0}, {1}", game.props.size, tile_set_name(texts, game.props.tiles)) }</td>
+                                    <td>{ texts.modes[mode_index(game.props.mode)] }</td>
+                                    <td>{ board.step }</td>
+                                    <td>{ format!("{} : {}", board.score.gamma, board.score.delta) }</td>
+                                    <td>
+                                        <button
+                                            class="action-button"
+                                            onclick=self.link.callback(move |_| MenuMsg::Resume(id))
+                                        >{
+                                            match board.state {
+                                                pyc::State::Pending => texts.resume,
+                                                _ => texts.view,
+                                            }
+                                        }</button>
+                                        <button
+                                            class="action-button"
+                                            onclick=self.link.callback(move |_| MenuMsg::Delete(id))
+                                        >{ texts.delete }</button>
+                                    </td>
+                                </tr>
+                            }
+                        })
+                        .collect::<Html>()
+                }
+            </table>
+        }
+    }
+
+    /// [`Html`] settings of the board, puzzles and lessons bring their own.
+    fn board_view(&self) -> Html {
+        if let Mode::Puzzle | Mode::Tutorial = self.props.mode {
+            return html! {};
+        }
+
+        let texts = self.props.locale.texts();
+
+        html! {
+            <>
+                <label>
+                    { texts.board_size }{ " " }
+                    <input
+                        type="number"
+                        min=pyc::SIZES.start().to_string()
+                        max=pyc::SIZES.end().to_string()
+                        value=self.props.size.to_string()
+                        oninput=self.link.callback(|e: InputData| MenuMsg::Size(e.value))
+                    />
+                </label>
+                <label>
+                    { texts.tiles }{ " " }
+                    <select onchange=self.link.callback(|data| MenuMsg::Tiles(selected(data)))>{
+                        pyc::TileSet::ALL
+                            .iter()
+                            .map(|tiles| html! {
+                                <option selected={ *tiles == self.props.tiles }>{ tile_set_name(texts, *tiles) }</option>
+                            })
+                            .collect::<Html>()
+                    }</select>
+                </label>
+            </>
+        }
+    }
+
+    /// [`Html`] settings of the bot, if there is one.
+    fn bot_view(&self) -> Html {
+        let (player, depth) = match self.props.mode {
+            Mode::Bot { player, depth } => (Some(player), depth),
+            Mode::Watch { depth } => (None, depth),
+            _ => return html! {},
+        };
+
+        let texts = self.props.locale.texts();
+
+        html! {
+            <>
+                {
+                    match player {
+                        Some(player) => html! {
+                            <label>
+                                { texts.bot_plays }{ " " }
+                                <select onchange=self.link.callback(|data| MenuMsg::BotPlayer(match selected(data) {
+                                    0 => pyc::Player::Gamma,
+                                    _ => pyc::Player::Delta,
+                                }))>
+                                    <option selected={ player == pyc::Player::Gamma }>{ texts.players[0] }</option>
+                                    <option selected={ player == pyc::Player::Delta }>{ texts.players[1] }</option>
+                                </select>
+                            </label>
+                        },
+                        None => html! {},
+                    }
+                }
+                <label>
+                    { texts.strength }{ " " }
+                    <select onchange=self.link.callback(|data| MenuMsg::Strength(selected(data)))>{
+                        STRENGTHS
+                            .iter()
+                            .zip(texts.strengths.iter())
+                            .map(|(strength, name)| html! {
+                                <option selected={ *strength == depth }>{ name }</option>
+                            })
+                            .collect::<Html>()
+                    }</select>
+                </label>
+            </>
+        }
+    }
+}
+
+/// Index of the selected option of a `<select>`.
+pub(crate) fn selected(data: ChangeData) -> usize {
+    match data {
+        ChangeData::Select(select) => select.selected_index().max(0) as usize,
+        _ => 0,
+    }
+}
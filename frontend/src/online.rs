@@ -0,0 +1,385 @@
+use super::{
+    clock,
+    i18n::{fill, Texts},
+    menu::tile_set_name,
+    GameMsg, Polycentrics,
+};
+use backend::prelude as pyc;
+use yew::{
+    format::Json,
+    prelude::*,
+    services::websocket::{WebSocketService, WebSocketStatus, WebSocketTask},
+};
+
+/// Connection to a game server, see `server/PROTOCOL.md`.
+// the server holds the real board, the local one is only a copy
+pub(crate) struct Online {
+    // dropping the task closes the socket
+    task: Option<WebSocketTask>,
+    connected: bool,
+    // form fields
+    url: String,
+    name: String,
+    game: String,
+    // the game and player, once seated
+    seat: Option<(u32, pyc::Player)>,
+    // the game and token of the last seat, kept after a disconnect to return to it
+    token: Option<(u32, String)>,
+    // the game watched without a seat
+    spectating: Option<u32>,
+    // games of the server, once requested
+    lobby: Vec<pyc::GameInfo>,
+    // last news for the player, like errors or the opponent
+    status: String,
+}
+
+/// Messages of the online mode.
+pub(crate) enum OnlineMsg {
+    // form fields
+    Url(String),
+    Name(String),
+    Game(String),
+    // open the socket
+    Connect,
+    // list the games of the server
+    Lobby,
+    // host a new game
+    Create,
+    // join the game of the form
+    Join,
+    // join a game of the lobby
+    JoinGame(u32),
+    // watch a game of the lobby
+    Spectate(u32),
+    Status(WebSocketStatus),
+    // `None` if the message could not be parsed
+    Received(Option<pyc::ServerMsg>),
+}
+
+impl Default for Online {
+    fn default() -> Self {
+        Self {
+            task: None,
+            connected: false,
+            // same as the default of the server
+            url: "ws://127.0.0.1:9001".to_string(),
+            name: String::new(),
+            game: String::new(),
+            seat: None,
+            token: None,
+            spectating: None,
+            lobby: Vec::new(),
+            status: String::new(),
+        }
+    }
+}
+
+impl Online {
+    /// The server holds the board, tiles are sent instead of set locally.
+    pub(crate) fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /// The own player, once seated.
+    pub(crate) fn player(&self) -> Option<pyc::Player> {
+        self.seat.map(|(_, player)| player)
+    }
+
+    /// Join `game`, returning to its seat if it was ours.
+    fn join(&mut self, game: u32) {
+        let token = match &self.token {
+            Some((seat, token)) if *seat == game => Some(token.clone()),
+            _ => None,
+        };
+
+        let msg = pyc::ClientMsg::Join {
+            game,
+            name: self.name.clone(),
+            token,
+        };
+        self.send(&msg);
+    }
+
+    /// Send a message to the server, if connected.
+    pub(crate) fn send(&mut self, msg: &pyc::ClientMsg) {
+        if let Some(task) = self.task.as_mut() {
+            task.send(Json(msg));
+        }
+    }
+}
+
+impl Polycentrics {
+    /// Updates the online mode based on an [`OnlineMsg`].
+    pub(crate) fn update_online(&mut self, msg: OnlineMsg) -> ShouldRender {
+        // the status keeps the language it was set in
+        let texts = self.texts();
+        let online = &mut self.online;
+
+        match msg {
+            OnlineMsg::Url(url) => online.url = url,
+            OnlineMsg::Name(name) => online.name = name,
+            OnlineMsg::Game(game) => online.game = game,
+            OnlineMsg::Connect => {
+                let callback = self.link.callback(|Json(msg): Json<Result<_, _>>| {
+                    GameMsg::Online(OnlineMsg::Received(msg.ok()))
+                });
+                let notification = self
+                    .link
+                    .callback(|status| GameMsg::Online(OnlineMsg::Status(status)));
+
+                match WebSocketService::connect_text(&online.url, callback, notification) {
+                    Ok(task) => {
+                        online.task = Some(task);
+                        online.status = texts.connecting.to_string();
+                    }
+                    Err(err) => online.status = err.to_string(),
+                }
+            }
+            OnlineMsg::Lobby => online.send(&pyc::ClientMsg::Lobby),
+            OnlineMsg::Create => {
+                let msg = pyc::ClientMsg::Create {
+                    name: online.name.clone(),
+                    size: self.board.points.len() as u8,
+                    tiles: self.props.tiles,
+                    clock: self.clock.as_ref().map(|clock| clock.control),
+                };
+                online.send(&msg);
+            }
+            OnlineMsg::Join => match online.game.trim().parse() {
+                Ok(game) => online.join(game),
+                Err(_) => online.status = fill(texts.invalid_game, &[&online.game]),
+            },
+            OnlineMsg::JoinGame(game) => online.join(game),
+            OnlineMsg::Spectate(game) => online.send(&pyc::ClientMsg::Spectate { game }),
+            OnlineMsg::Status(status) => {
+                online.connected = matches!(status, WebSocketStatus::Opened);
+                online.status = match status {
+                    WebSocketStatus::Opened => texts.connected,
+                    WebSocketStatus::Closed => texts.disconnected,
+                    WebSocketStatus::Error => texts.connection_failed,
+                }
+                .to_string();
+
+                if !online.connected {
+                    online.task = None;
+                    online.seat = None;
+                    online.spectating = None;
+                    online.lobby.clear();
+
+                    // the game of the server is gone, play locally again
+                    self.cancel_thinking();
+                    self.record = self.props.record();
+                    self.board = self.record.start();
+                    self.clock = self
+                        .clock
+                        .as_ref()
+                        .map(|clock| pyc::Clock::new(clock.control));
+                    self.hint = None;
+                    self.preview = None;
+                    self.selected = None;
+                }
+            }
+            OnlineMsg::Received(None) => online.status = texts.invalid_message.to_string(),
+            OnlineMsg::Received(Some(msg)) => match msg {
+                pyc::ServerMsg::Lobby { games } => {
+                    online.status = fill(texts.games, &[&games.len()]);
+                    online.lobby = games;
+                }
+                pyc::ServerMsg::Seated {
+                    game,
+                    player,
+                    token,
+                    record,
+                } => {
+                    // the steps and actions that follow extend the record
+                    self.record = record;
+                    online.seat = Some((game, player));
+                    online.token = Some((game, token));
+                    online.lobby.clear();
+                    online.status = fill(texts.waiting, &[&game]);
+                }
+                pyc::ServerMsg::Spectating { game, record } => {
+                    online.spectating = Some(game);
+                    online.lobby.clear();
+                    online.status = fill(texts.watching, &[&game, &(record.moves.len() + 1)]);
+                    self.record = record;
+                }
+                pyc::ServerMsg::Abandoned { player, seconds } => {
+                    online.status = fill(texts.abandoned, &[&player_name(texts, player), &seconds]);
+                }
+                pyc::ServerMsg::Returned { player } => {
+                    online.status = fill(texts.returned, &[&player_name(texts, player)]);
+                }
+                pyc::ServerMsg::Start { gamma, delta } => {
+                    online.status = fill(texts.versus, &[&gamma, &delta]);
+                }
+                pyc::ServerMsg::Update { board } => {
+                    self.cancel_thinking();
+                    let before = std::mem::replace(&mut self.board, board);
+                    self.announce(&before);
+                    self.hint = None;
+                    self.preview = None;
+                    self.selected = None;
+                }
+                pyc::ServerMsg::Clock {
+                    control,
+                    gamma,
+                    delta,
+                    running,
+                } => {
+                    // the local clock only counts down until the next message
+                    self.clock = Some(pyc::Clock {
+                        control,
+                        gamma,
+                        delta,
+                        running: running.map(|player| (player, clock::now())),
+                    });
+                }
+                pyc::ServerMsg::Action { action } => {
+                    self.record.moves.push(action);
+                    online.status = match action {
+                        pyc::Action::Resign(player) => {
+                            fill(texts.resigned, &[&player_name(texts, player)])
+                        }
+                        pyc::Action::OfferDraw(player) => {
+                            fill(texts.offers_draw, &[&player_name(texts, player)])
+                        }
+                        pyc::Action::AcceptDraw(_) => texts.draw_agreed.to_string(),
+                        pyc::Action::DeclineDraw(player) => {
+                            fill(texts.draw_declined, &[&player_name(texts, player)])
+                        }
+                        // sent as steps
+                        pyc::Action::Tile(_) => return false,
+                    }
+                }
+                // the update right after shows the outcome
+                pyc::ServerMsg::Step { tile, .. } => {
+                    self.record.moves.push(pyc::Action::Tile(tile));
+                    return false;
+                }
+                pyc::ServerMsg::Error { message } => online.status = message,
+            },
+        }
+
+        true
+    }
+
+    /// [`Html`] view of the connection to a game server.
+    pub(crate) fn online_view(&self) -> Html {
+        let online = &self.online;
+        let texts = self.texts();
+
+        html! {
+            <div class="online">
+                {
+                    if !online.connected {
+                        html! {
+                            <>
+                                <input
+                                    placeholder=texts.server
+                                    value=online.url.clone()
+                                    oninput=self.link.callback(|e: InputData| GameMsg::Online(OnlineMsg::Url(e.value)))
+                                />
+                                <button
+                                    class="action-button"
+                                    disabled=online.task.is_some()
+                                    onclick=self.link.callback(|_| GameMsg::Online(OnlineMsg::Connect))
+                                >{ texts.connect }</button>
+                            </>
+                        }
+                    } else if online.seat.is_none() && online.spectating.is_none() {
+                        html! {
+                            <>
+                                <button
+                                    class="action-button"
+                                    onclick=self.link.callback(|_| GameMsg::Online(OnlineMsg::Lobby))
+                                >{ texts.lobby }</button>
+                                <input
+                                    placeholder=texts.name
+                                    value=online.name.clone()
+                                    oninput=self.link.callback(|e: InputData| GameMsg::Online(OnlineMsg::Name(e.value)))
+                                />
+                                <button
+                                    class="action-button"
+                                    onclick=self.link.callback(|_| GameMsg::Online(OnlineMsg::Create))
+                                >{ texts.create }</button>
+                                <input
+                                    placeholder=texts.game
+                                    value=online.game.clone()
+                                    oninput=self.link.callback(|e: InputData| GameMsg::Online(OnlineMsg::Game(e.value)))
+                                />
+                                <button
+                                    class="action-button"
+                                    onclick=self.link.callback(|_| GameMsg::Online(OnlineMsg::Join))
+                                >{ texts.join }</button>
+                            </>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+                <span class="online-status">{ &online.status }</span>
+                { self.lobby_view() }
+            </div>
+        }
+    }
+
+    /// [`Html`] table of the games of the server.
+    fn lobby_view(&self) -> Html {
+        if self.online.lobby.is_empty() {
+            return html! {};
+        }
+
+        let texts = self.texts();
+
+        html! {
+            <table class="lobby">
+                <tr>
+                    <th>{ texts.game }</th>
+                    <th>{ texts.size }</th>
+                    <th>{ texts.tiles }</th>
+                    <th>{ texts.players[0] }</th>
+                    <th>{ texts.players[1] }</th>
+                    <th>{ texts.step_column }</th>
+                    <th/>
+                </tr>
+                {
+                    self.online.lobby.iter().map(|info| {
+                        let game = info.game;
+                        // a running game can still be joined with our token
+                        let ours = matches!(&self.online.token, Some((seat, _)) if *seat == game);
+
+                        html! {
+                            <tr>
+                                <td>{ game }</td>
+                                <td>{ format!("{0}x{0}", info.size) }</td>
+                                <td>{ tile_set_name(texts, info.tiles) }</td>
+                                <td>{ info.gamma.as_deref().unwrap_or("-") }</td>
+                                <td>{ info.delta.as_deref().unwrap_or("-") }</td>
+                                <td>{ info.step }</td>
+                                <td>
+                                    <button
+                                        class="action-button"
+                                        disabled=!(info.open || ours)
+                                        onclick=self.link.callback(move |_| GameMsg::Online(OnlineMsg::JoinGame(game)))
+                                    >{ texts.join }</button>
+                                    <button
+                                        class="action-button"
+                                        onclick=self.link.callback(move |_| GameMsg::Online(OnlineMsg::Spectate(game)))
+                                    >{ fill(texts.watch, &[&info.spectators]) }</button>
+                                </td>
+                            </tr>
+                        }
+                    }).collect::<Html>()
+                }
+            </table>
+        }
+    }
+}
+
+pub(crate) fn player_name(texts: &Texts, player: pyc::Player) -> &'static str {
+    match player {
+        pyc::Player::Gamma => texts.players[0],
+        pyc::Player::Delta => texts.players[1],
+    }
+}
@@ -0,0 +1,77 @@
+use super::{arrow_tip, Polycentrics};
+use backend::prelude as pyc;
+use yew::prelude::*;
+
+impl Polycentrics {
+    /// The board after setting `tile`, to preview it while hovered.
+    pub(crate) fn preview(&self, tile: usize) -> Option<pyc::Board> {
+        if tile >= self.board.options().len() || !matches!(self.board.state, pyc::State::Pending) {
+            return None;
+        }
+
+        let mut board = self.board.clone();
+        board.step(tile);
+        Some(board)
+    }
+
+    /// Render the previewed tile to SVG: its curve, the next arrow
+    /// and the points it would capture.
+    // drawn above the board, but below the current arrow
+    pub(crate) fn preview_svg(&self) -> Html {
+        let next = match &self.preview {
+            Some(next) => next,
+            None => return html! {},
+        };
+
+        // the board ends the game if the arrow leaves it
+        let size = self.board.points.len() as i8;
+        let pos = next.arrow.pos;
+        let outside = pos.x < 0 || pos.y < 0 || pos.x >= size || pos.y >= size;
+
+        let curve = match next.path.last() {
+            Some(curve) => curve,
+            None => return html! {},
+        };
+        let (x2, y2) = arrow_tip(&next.arrow);
+
+        html! {
+            <g class=classes!("preview", outside.then_some("preview-outside"))>
+                <path
+                    class="curve"
+                    d=format!("M {} {} Q {} {} {} {}", curve.start.x, curve.start.y, curve.mid.x, curve.mid.y, curve.end.x, curve.end.y)
+                />
+                <line
+                    class="arrow"
+                    x1=pos.x.to_string() y1=pos.y.to_string()
+                    x2=x2.to_string() y2=y2.to_string()
+                />
+                {
+                    // points free now, but owned after the step
+                    self.board
+                        .points
+                        .iter()
+                        .zip(next.points.iter())
+                        .enumerate()
+                        .flat_map(|(j, (now, next))| {
+                            now.iter().zip(next.iter()).enumerate().filter_map(move |(i, points)| {
+                                match points {
+                                    (None, Some(player)) => Some(html! {
+                                        <circle
+                                            class=classes!("point", "preview-capture", match player {
+                                                pyc::Player::Gamma => "point-gamma",
+                                                pyc::Player::Delta => "point-delta",
+                                            })
+                                            cx=i.to_string() cy=j.to_string()
+                                            r="0.2"
+                                        />
+                                    }),
+                                    _ => None,
+                                }
+                            })
+                        })
+                        .collect::<Html>()
+                }
+            </g>
+        }
+    }
+}
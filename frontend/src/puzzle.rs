@@ -0,0 +1,201 @@
+//! Puzzles to find the best tile of a position, from the bundled puzzle file.
+
+use super::{camera::Camera, i18n::fill, online::player_name, GameMsg, Polycentrics};
+use backend::prelude as pyc;
+use yew::prelude::*;
+
+// generated with `pyc puzzle generate 2 2 5`
+const PUZZLES: &str = include_str!("../assets/puzzles.txt");
+
+/// The puzzle being solved, and the others to choose from.
+pub(crate) struct Puzzles {
+    pub puzzles: Vec<pyc::Puzzle>,
+    pub index: usize,
+    // tiles of the solution done so far, with the answers
+    pub progress: usize,
+    // the last tile tried was not the one of the solution
+    pub missed: bool,
+}
+
+/// Messages of the [`Puzzles`].
+pub(crate) enum PuzzleMsg {
+    Previous,
+    Next,
+    // start the puzzle over
+    Retry,
+}
+
+impl Puzzles {
+    /// The bundled puzzles, starting with the first.
+    pub(crate) fn load() -> Self {
+        Self {
+            // a broken file only costs the puzzles, so do not fail on it
+            puzzles: pyc::Puzzle::decode_all(PUZZLES).unwrap_or_default(),
+            index: 0,
+            progress: 0,
+            missed: false,
+        }
+    }
+
+    fn puzzle(&self) -> Option<&pyc::Puzzle> {
+        self.puzzles.get(self.index)
+    }
+
+    /// The next tile of the solution, `None` once solved.
+    pub(crate) fn expected(&self) -> Option<usize> {
+        self.puzzle()
+            .and_then(|puzzle| puzzle.solution.get(self.progress))
+            .map(|tile| *tile as usize)
+    }
+
+    pub(crate) fn solved(&self) -> bool {
+        self.puzzle().is_some() && self.expected().is_none()
+    }
+}
+
+impl Polycentrics {
+    /// Set up the current puzzle from its start.
+    pub(crate) fn start_puzzle(&mut self) {
+        let puzzles = match self.puzzles.as_mut() {
+            Some(puzzles) => puzzles,
+            None => return,
+        };
+
+        puzzles.progress = 0;
+        puzzles.missed = false;
+
+        if let Some(puzzle) = puzzles.puzzles.get(puzzles.index) {
+            self.record = puzzle.record.clone();
+            // puzzles are read with `Record::decode`, which replays them
+            self.board = puzzle.start().unwrap();
+            self.camera = Camera::board(self.record.size as usize);
+        }
+
+        self.hint = None;
+        self.preview = None;
+        self.selected = None;
+        self.viewer = None;
+        self.announcement.clear();
+    }
+
+    /// Set `tile` if it is the next of the solution, and answer it.
+    pub(crate) fn solve(&mut self, tile: usize) {
+        let expected = match self.puzzles.as_ref().and_then(Puzzles::expected) {
+            Some(expected) => expected,
+            None => return,
+        };
+
+        // tiles of the same shape are the same answer, as in `Puzzle::validate`
+        let shape = |tile: usize| {
+            self.board
+                .options()
+                .get(tile)
+                .map(|curve| curve.shape(pyc::Direction::North))
+        };
+
+        if shape(tile) != shape(expected) {
+            if let Some(puzzles) = self.puzzles.as_mut() {
+                puzzles.missed = true;
+            }
+
+            self.preview = None;
+            self.announcement = self.texts().not_best.to_string();
+            return;
+        }
+
+        // the tile of the solution, so the indices of the answers stay right
+        self.act(pyc::Action::Tile(expected as u8));
+        let answer = self.puzzles.as_mut().and_then(|puzzles| {
+            puzzles.progress += 1;
+            puzzles.missed = false;
+            puzzles.expected()
+        });
+
+        // the answers are part of the puzzle, not the choice of a bot
+        if let Some(answer) = answer {
+            let announcement = std::mem::take(&mut self.announcement);
+            self.act(pyc::Action::Tile(answer as u8));
+            self.announcement = format!("{} {}", announcement, self.announcement);
+
+            if let Some(puzzles) = self.puzzles.as_mut() {
+                puzzles.progress += 1;
+            }
+        }
+
+        if self.puzzles.as_ref().is_some_and(Puzzles::solved) {
+            self.announcement = format!("{} {}", self.announcement, self.texts().solved);
+        }
+    }
+
+    /// Updates the puzzles based on a [`PuzzleMsg`].
+    pub(crate) fn update_puzzle(&mut self, msg: PuzzleMsg) -> ShouldRender {
+        let puzzles = match self.puzzles.as_mut() {
+            Some(puzzles) => puzzles,
+            None => return false,
+        };
+
+        match msg {
+            PuzzleMsg::Previous => puzzles.index = puzzles.index.saturating_sub(1),
+            PuzzleMsg::Next => {
+                puzzles.index = (puzzles.index + 1).min(puzzles.puzzles.len().saturating_sub(1))
+            }
+            PuzzleMsg::Retry => {}
+        }
+
+        self.start_puzzle();
+        true
+    }
+
+    /// [`Html`] task and controls of the puzzle, instead of the actions.
+    pub(crate) fn puzzle_view(&self) -> Html {
+        let puzzles = match &self.puzzles {
+            Some(puzzles) => puzzles,
+            None => return html! {},
+        };
+
+        let texts = self.texts();
+        let puzzle = match puzzles.puzzle() {
+            Some(puzzle) => puzzle,
+            None => return html! { <div class="puzzle">{ texts.no_puzzles }</div> },
+        };
+
+        let last = puzzles.puzzles.len() - 1;
+        let task = if puzzles.solved() {
+            texts.solved.to_string()
+        } else if puzzles.missed {
+            texts.not_best.to_string()
+        } else {
+            fill(texts.find_best, &[&player_name(texts, self.board.active)])
+        };
+        let goal = fill(texts.puzzle_goal, &[&puzzle.points]);
+
+        html! {
+            <div class="puzzle">
+                <span class="puzzle-title">{
+                    fill(texts.puzzle_title, &[&(puzzles.index + 1), &(last + 1), &goal])
+                }</span>
+                <span class=classes!("puzzle-task", puzzles.solved().then_some("puzzle-solved"))>{ task }</span>
+                <button
+                    class="action-button"
+                    disabled={ puzzles.index == 0 }
+                    onclick=self.link.callback(|_| GameMsg::Puzzle(PuzzleMsg::Previous))
+                >{ texts.previous }</button>
+                <button
+                    class="action-button"
+                    disabled={ puzzles.solved() }
+                    onclick=self.link.callback(|_| GameMsg::Hint)
+                >{ texts.hint }</button>
+                <button
+                    class="action-button"
+                    disabled={ puzzles.progress == 0 && !puzzles.missed }
+                    onclick=self.link.callback(|_| GameMsg::Puzzle(PuzzleMsg::Retry))
+                >{ texts.retry }</button>
+                <button
+                    class="action-button"
+                    disabled={ puzzles.index == last }
+                    onclick=self.link.callback(|_| GameMsg::Puzzle(PuzzleMsg::Next))
+                >{ texts.next }</button>
+            </div>
+        }
+    }
+}
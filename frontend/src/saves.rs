@@ -0,0 +1,132 @@
+//! Local games kept in `localStorage`, to resume them after a reload.
+
+use super::{clock, GameProps, Mode, Polycentrics};
+use backend::prelude as pyc;
+use serde::{Deserialize, Serialize};
+use yew::{
+    format::Json,
+    services::{storage::Area, StorageService},
+};
+
+/// A local game as its configuration and moves, replayed when resumed.
+// not the `Board`, so saves still load after its fields change
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct SavedGame {
+    // when the game started, also tells the saves apart
+    pub id: pyc::Millis,
+    // when the last move was saved
+    pub saved: pyc::Millis,
+    pub props: GameProps,
+    pub record: pyc::Record,
+    // stopped at the last save, `None` for untimed games
+    pub clock: Option<pyc::Clock>,
+}
+
+impl SavedGame {
+    /// The board at the save, lost by the player whose clock ran out.
+    pub(crate) fn board(&self) -> Option<pyc::Board> {
+        let mut board = self.record.replay().ok()?;
+
+        // the clock was stopped at the save, so its times are final
+        if let (Some(clock), pyc::State::Pending) = (&self.clock, &board.state) {
+            if clock.gamma == 0 {
+                board.state = pyc::State::Victory(pyc::Player::Delta);
+            } else if clock.delta == 0 {
+                board.state = pyc::State::Victory(pyc::Player::Gamma);
+            }
+        }
+
+        Some(board)
+    }
+}
+
+// key in `localStorage`
+const KEY: &str = "polycentrics.games";
+
+// oldest saves are dropped beyond this
+const MAX_SAVES: usize = 20;
+
+/// The saved games, the last saved first.
+pub(crate) fn load() -> Vec<SavedGame> {
+    let storage = match StorageService::new(Area::Local) {
+        Ok(storage) => storage,
+        Err(_) => return Vec::new(),
+    };
+
+    let Json(games) = storage.restore(KEY);
+    let mut games: Vec<SavedGame> = games.unwrap_or_default();
+
+    // a save of an older version might not replay
+    games.retain(|game| game.record.is_valid());
+    games.sort_by_key(|game| std::cmp::Reverse(game.saved));
+    games
+}
+
+fn store(games: &[SavedGame]) {
+    if let Ok(mut storage) = StorageService::new(Area::Local) {
+        storage.store(KEY, Json(&games));
+    }
+}
+
+/// Forget the game with `id`.
+pub(crate) fn delete(id: pyc::Millis) {
+    let mut games = load();
+    games.retain(|game| game.id != id);
+    store(&games);
+}
+
+impl Polycentrics {
+    /// Save the game after a move, replacing its earlier save.
+    // online games are kept by the server, puzzles and lessons are not continued
+    pub(crate) fn save(&self) {
+        if let Mode::Online | Mode::Puzzle | Mode::Tutorial = self.props.mode {
+            return;
+        }
+
+        let now = clock::now();
+        let clock = self.clock.clone().map(|mut clock| {
+            clock.stop(now);
+            clock
+        });
+
+        let mut games = load();
+        games.retain(|game| game.id != self.save_id);
+        games.insert(
+            0,
+            SavedGame {
+                id: self.save_id,
+                saved: now,
+                props: GameProps {
+                    resume: None,
+                    ..self.props.clone()
+                },
+                record: self.record.clone(),
+                clock,
+            },
+        );
+        games.truncate(MAX_SAVES);
+
+        store(&games);
+    }
+
+    /// Continue the saved game with `id`, if it still exists.
+    pub(crate) fn resume(&mut self, id: pyc::Millis) {
+        let game = match load().into_iter().find(|game| game.id == id) {
+            Some(game) => game,
+            None => return,
+        };
+
+        // `load` drops the games which don't replay
+        self.board = game.board().unwrap();
+        self.record = game.record;
+        self.save_id = game.id;
+
+        // the time while away is not counted
+        self.clock = game.clock.map(|mut clock| {
+            if !self.record.moves.is_empty() && matches!(self.board.state, pyc::State::Pending) {
+                clock.start(self.board.active, clock::now());
+            }
+            clock
+        });
+    }
+}
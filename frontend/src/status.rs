@@ -0,0 +1,83 @@
+use super::{i18n::fill, online::player_name, GameMsg, Polycentrics};
+use backend::prelude as pyc;
+use yew::prelude::*;
+
+impl Polycentrics {
+    /// [`Html`] panel of the score, whose turn it is and the tiles left.
+    // colours come from the same classes as the points
+    pub(crate) fn status_view(&self) -> Html {
+        let board = self.shown();
+        let texts = self.texts();
+
+        let player = |player: pyc::Player| {
+            let (name, class, points) = match player {
+                pyc::Player::Gamma => (texts.players[0], "status-gamma", board.score.gamma),
+                pyc::Player::Delta => (texts.players[1], "status-delta", board.score.delta),
+            };
+
+            let active = board.active == player
+                && matches!(board.state, pyc::State::Pending);
+
+            html! {
+                <div class=classes!("status-player", class, active.then_some("status-active"))>
+                    <span class="status-name">{ name }</span>
+                    <span class="status-points">{ points }</span>
+                </div>
+            }
+        };
+
+        html! {
+            <div class="status">
+                { player(pyc::Player::Gamma) }
+                <div class="status-game">
+                    <span>{ fill(texts.step, &[&board.step]) }</span>
+                    <span>{ fill(texts.tiles_left, &[&board.tiles.len()]) }</span>
+                </div>
+                { player(pyc::Player::Delta) }
+            </div>
+        }
+    }
+
+    /// [`Html`] overlay of the result, once the game is over.
+    // not while viewing, its controls show the result
+    pub(crate) fn game_over_view(&self) -> Html {
+        if self.viewer.is_some() {
+            return html! {};
+        }
+
+        let board = &self.board;
+        let texts = self.texts();
+
+        let (text, class) = match board.state {
+            pyc::State::Pending => return html! {},
+            pyc::State::Victory(player) => (
+                fill(texts.won_game, &[&player_name(texts, player)]),
+                match player {
+                    pyc::Player::Gamma => "status-gamma",
+                    pyc::Player::Delta => "status-delta",
+                },
+            ),
+            pyc::State::Draw => (texts.draw.to_string(), ""),
+        };
+
+        html! {
+            <div class="game-over">
+                <span class=classes!("game-over-result", class)>{ text }</span>
+                <span>{ format!("{} : {}", board.score.gamma, board.score.delta) }</span>
+                {
+                    // online, the server decides about the next game
+                    if self.online.is_connected() {
+                        html! {}
+                    } else {
+                        html! {
+                            <button
+                                class="action-button"
+                                onclick=self.link.callback(|_| GameMsg::Restart)
+                            >{ texts.play_again }</button>
+                        }
+                    }
+                }
+            </div>
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! Colour themes of the board and the page, kept in `localStorage`.
+
+use super::{i18n::Texts, menu::selected, GameMsg, Polycentrics};
+use backend::prelude as pyc;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+use yew::{
+    format::Json,
+    prelude::*,
+    services::{storage::Area, StorageService},
+    web_sys::HtmlElement,
+};
+
+/// The look the player colours are put on.
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub(crate) enum Base {
+    #[default]
+    Light,
+    Dark,
+    HighContrast,
+    Print,
+}
+
+impl Base {
+    const ALL: [Base; 4] = [Base::Light, Base::Dark, Base::HighContrast, Base::Print];
+
+    fn name(self, texts: &Texts) -> &'static str {
+        match self {
+            Base::Light => texts.themes[0],
+            Base::Dark => texts.themes[1],
+            Base::HighContrast => texts.themes[2],
+            Base::Print => texts.themes[3],
+        }
+    }
+
+    /// Class of the page, for what the board colours do not cover.
+    fn class(self) -> &'static str {
+        match self {
+            Base::Light => "theme-light",
+            Base::Dark => "theme-dark",
+            Base::HighContrast => "theme-high-contrast",
+            Base::Print => "theme-print",
+        }
+    }
+}
+
+/// Theme of the frontend, see [`Theme::svg_style`].
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub(crate) struct Theme {
+    pub base: Base,
+    // colours of the players chosen by the user, `None` for the base ones
+    pub gamma: Option<String>,
+    pub delta: Option<String>,
+    // blue and orange as player colours, unless chosen
+    pub colour_blind: bool,
+}
+
+/// Messages of the [`Theme`].
+pub(crate) enum ThemeMsg {
+    // index into `Base::ALL`
+    Base(usize),
+    Gamma(String),
+    Delta(String),
+    ResetColours,
+    ColourBlind,
+    // download the board as SVG
+    Export,
+}
+
+// key in `localStorage`
+const KEY: &str = "polycentrics.theme";
+
+impl Theme {
+    /// The theme stored in `localStorage`, or the default one.
+    pub(crate) fn load() -> Self {
+        let storage = match StorageService::new(Area::Local) {
+            Ok(storage) => storage,
+            Err(_) => return Self::default(),
+        };
+
+        let Json(theme) = storage.restore(KEY);
+        theme.unwrap_or_default()
+    }
+
+    /// Keep the theme for the next visit, if `localStorage` is available.
+    fn save(&self) {
+        if let Ok(mut storage) = StorageService::new(Area::Local) {
+            storage.store(KEY, Json(self));
+        }
+    }
+
+    /// Colours and sizes of the board.
+    // the single source of the colours, used for the page and for exports,
+    // so both look the same
+    pub(crate) fn svg_style(&self) -> pyc::SvgStyle {
+        let colour = |colour: &str| colour.to_string();
+
+        let mut style = match self.base {
+            Base::Light => pyc::SvgStyle {
+                background: Some(colour("white")),
+                ..Default::default()
+            },
+            Base::Dark => pyc::SvgStyle {
+                background: Some(colour("#1e1e1e")),
+                point: colour("#e0e0e0"),
+                gamma: colour("#6fa8ff"),
+                delta: colour("#5fd35f"),
+                curve: colour("#e0e0e0"),
+                arrow: colour("#ff6b6b"),
+                capture: colour("#ffb347"),
+                ..Default::default()
+            },
+            Base::HighContrast => pyc::SvgStyle {
+                background: Some(colour("black")),
+                point: colour("white"),
+                gamma: colour("#00ffff"),
+                delta: colour("#ffff00"),
+                curve: colour("white"),
+                arrow: colour("#ff4040"),
+                capture: colour("#ff00ff"),
+                curve_width: 0.15,
+                arrow_width: 0.15,
+                point_radius: 0.15,
+                ..Default::default()
+            },
+            // players told apart without colour
+            Base::Print => pyc::SvgStyle {
+                background: Some(colour("white")),
+                point: colour("#bbbbbb"),
+                gamma: colour("black"),
+                delta: colour("#777777"),
+                curve: colour("black"),
+                arrow: colour("black"),
+                capture: colour("#cccccc"),
+                curve_width: 0.08,
+                ..Default::default()
+            },
+        };
+
+        if self.colour_blind {
+            // Okabe-Ito blue and orange, told apart by every kind of colour blindness
+            style.gamma = colour("#0072b2");
+            style.delta = colour("#e69f00");
+        }
+
+        if let Some(gamma) = &self.gamma {
+            style.gamma = gamma.clone();
+        }
+        if let Some(delta) = &self.delta {
+            style.delta = delta.clone();
+        }
+
+        style
+    }
+
+    /// Colour of text and the overlay behind the result.
+    fn page(&self) -> (&'static str, &'static str) {
+        match self.base {
+            Base::Light | Base::Print => ("black", "rgba(255, 255, 255, 0.75)"),
+            Base::Dark => ("#e0e0e0", "rgba(30, 30, 30, 0.75)"),
+            Base::HighContrast => ("white", "rgba(0, 0, 0, 0.85)"),
+        }
+    }
+
+    /// CSS variables of the theme, used by `default.css`.
+    fn css(&self) -> String {
+        let style = self.svg_style();
+        let (foreground, overlay) = self.page();
+
+        format!(
+            "--background: {}; --foreground: {}; --overlay: {}; --point: {}; --gamma: {}; --delta: {}; --curve: {}; --arrow: {}; --capture: {}; --curve-width: {}; --arrow-width: {}; --point-radius: {};",
+            style.background.as_deref().unwrap_or("transparent"),
+            foreground,
+            overlay,
+            style.point,
+            style.gamma,
+            style.delta,
+            style.curve,
+            style.arrow,
+            style.capture,
+            style.curve_width,
+            style.arrow_width,
+            style.point_radius,
+        )
+    }
+}
+
+/// Let the browser download `svg` as a file.
+fn download(svg: &str) {
+    let document = yew::utils::document();
+
+    if let Ok(link) = document.create_element("a") {
+        let href = format!(
+            "data:image/svg+xml;charset=utf-8,{}",
+            String::from(js_sys::encode_uri_component(svg))
+        );
+
+        let _ = link.set_attribute("href", &href);
+        let _ = link.set_attribute("download", "polycentrics.svg");
+
+        if let Ok(link) = link.dyn_into::<HtmlElement>() {
+            link.click();
+        }
+    }
+}
+
+impl Polycentrics {
+    /// Updates the theme based on a [`ThemeMsg`].
+    pub(crate) fn update_theme(&mut self, msg: ThemeMsg) -> ShouldRender {
+        if let ThemeMsg::Export = msg {
+            // the board on screen, also while viewing a move
+            download(&self.shown().svg(&self.theme.svg_style()));
+            return false;
+        }
+
+        let theme = &mut self.theme;
+
+        match msg {
+            ThemeMsg::Base(index) => {
+                if let Some(base) = Base::ALL.get(index) {
+                    theme.base = *base;
+                }
+            }
+            ThemeMsg::Gamma(colour) => theme.gamma = Some(colour),
+            ThemeMsg::Delta(colour) => theme.delta = Some(colour),
+            ThemeMsg::ResetColours => {
+                theme.gamma = None;
+                theme.delta = None;
+            }
+            ThemeMsg::ColourBlind => theme.colour_blind = !theme.colour_blind,
+            ThemeMsg::Export => {}
+        }
+
+        theme.save();
+        true
+    }
+
+    /// Class and CSS variables of the page.
+    pub(crate) fn theme_attributes(&self) -> (&'static str, String) {
+        (self.theme.base.class(), self.theme.css())
+    }
+
+    /// [`Html`] settings of the theme.
+    pub(crate) fn theme_view(&self) -> Html {
+        let style = self.theme.svg_style();
+        let texts = self.texts();
+
+        html! {
+            <div class="theme">
+                <label>
+                    { texts.theme }{ " " }
+                    <select onchange=self.link.callback(|data| GameMsg::Theme(ThemeMsg::Base(selected(data))))>{
+                        Base::ALL
+                            .iter()
+                            .map(|base| html! {
+                                <option selected={ *base == self.theme.base }>{ base.name(texts) }</option>
+                            })
+                            .collect::<Html>()
+                    }</select>
+                </label>
+                <label>
+                    { texts.players[0] }{ " " }
+                    <input
+                        type="color"
+                        // the input only takes hex colours
+                        value=hex(&style.gamma)
+                        oninput=self.link.callback(|e: InputData| GameMsg::Theme(ThemeMsg::Gamma(e.value)))
+                    />
+                </label>
+                <label>
+                    { texts.players[1] }{ " " }
+                    <input
+                        type="color"
+                        value=hex(&style.delta)
+                        oninput=self.link.callback(|e: InputData| GameMsg::Theme(ThemeMsg::Delta(e.value)))
+                    />
+                </label>
+                <button
+                    class="action-button"
+                    disabled={ self.theme.gamma.is_none() && self.theme.delta.is_none() }
+                    onclick=self.link.callback(|_| GameMsg::Theme(ThemeMsg::ResetColours))
+                >{ texts.reset_colours }</button>
+                <label>
+                    <input
+                        type="checkbox"
+                        checked=self.theme.colour_blind
+                        onclick=self.link.callback(|_| GameMsg::Theme(ThemeMsg::ColourBlind))
+                    />
+                    { " " }{ texts.colour_blind }
+                </label>
+                <button
+                    class="action-button"
+                    onclick=self.link.callback(|_| GameMsg::Theme(ThemeMsg::Export))
+                >{ texts.export_svg }</button>
+            </div>
+        }
+    }
+}
+
+/// Hex notation of the named colours of the themes, for colour inputs.
+fn hex(colour: &str) -> String {
+    match colour {
+        "black" => "#000000",
+        "white" => "#ffffff",
+        "blue" => "#0000ff",
+        "green" => "#008000",
+        "red" => "#ff0000",
+        colour => colour,
+    }
+    .to_string()
+}
@@ -0,0 +1,208 @@
+//! Guided lessons on the rules, with scripted boards and only some tiles allowed.
+
+use super::{camera::Camera, i18n::fill, GameMsg, Polycentrics};
+use backend::prelude as pyc;
+use yew::prelude::*;
+
+/// The lesson being played.
+pub(crate) struct Tutorial {
+    // index into `LESSONS`
+    pub lesson: usize,
+    // the tile of the lesson was set
+    pub done: bool,
+}
+
+/// Messages of the [`Tutorial`].
+pub(crate) enum TutorialMsg {
+    Previous,
+    Next,
+    // start the lesson over
+    Retry,
+}
+
+/// One rule to learn by setting a tile, its text is in `Texts::lessons`.
+struct Lesson {
+    // tiles set on a board of `SIZE` before the lesson starts
+    moves: &'static [u8],
+    // the tiles the lesson lets set, by index into `Board::options()`
+    allowed: fn(&pyc::Board, usize) -> bool,
+}
+
+// small, so the border is close
+const SIZE: u8 = 9;
+
+// the order of the README tutorial
+const LESSONS: [Lesson; 4] = [
+    Lesson {
+        moves: &[],
+        allowed: any,
+    },
+    Lesson {
+        moves: &[],
+        allowed: turns_right,
+    },
+    Lesson {
+        moves: &[0, 1],
+        allowed: captures,
+    },
+    Lesson {
+        moves: &[1, 1],
+        allowed: stays,
+    },
+];
+
+fn any(_: &pyc::Board, _: usize) -> bool {
+    true
+}
+
+// tiles of the set start at the origin facing north
+fn turns_right(board: &pyc::Board, tile: usize) -> bool {
+    board.options()[tile].end.x > 0
+}
+
+fn captures(board: &pyc::Board, tile: usize) -> bool {
+    let mut next = board.clone();
+    next.step(tile);
+
+    next.captures.len() > board.captures.len()
+}
+
+fn stays(board: &pyc::Board, tile: usize) -> bool {
+    let mut next = board.clone();
+    next.step(tile);
+
+    matches!(next.state, pyc::State::Pending)
+}
+
+impl Polycentrics {
+    /// Set up the current lesson from its start.
+    pub(crate) fn start_lesson(&mut self) {
+        let tutorial = match self.tutorial.as_mut() {
+            Some(tutorial) => tutorial,
+            None => return,
+        };
+
+        tutorial.done = false;
+        let index = tutorial.lesson;
+        let lesson = &LESSONS[index];
+
+        self.record = pyc::Record {
+            moves: lesson
+                .moves
+                .iter()
+                .map(|tile| pyc::Action::Tile(*tile))
+                .collect(),
+            ..pyc::Record::with_size(SIZE)
+        };
+        // the moves of the lessons are fixed
+        self.board = self.record.replay().unwrap();
+        self.camera = Camera::board(SIZE as usize);
+
+        self.hint = None;
+        self.preview = None;
+        self.selected = None;
+        self.viewer = None;
+        self.announcement = self.texts().lessons[index].text.to_string();
+    }
+
+    /// The lesson lets the user set `tile`, every tile outside of the tutorial.
+    pub(crate) fn allowed(&self, tile: usize) -> bool {
+        match &self.tutorial {
+            Some(tutorial) => {
+                tile < self.board.options().len()
+                    && (LESSONS[tutorial.lesson].allowed)(&self.board, tile)
+            }
+            None => true,
+        }
+    }
+
+    /// The first tile the lesson allows, as a hint.
+    pub(crate) fn lesson_hint(&self) -> Option<usize> {
+        (0..self.board.options().len()).find(|tile| self.allowed(*tile))
+    }
+
+    /// Set `tile` if the lesson allows it.
+    pub(crate) fn learn(&mut self, tile: usize) {
+        let lesson = match &self.tutorial {
+            Some(tutorial) if !tutorial.done => &self.texts().lessons[tutorial.lesson],
+            _ => return,
+        };
+
+        if !self.allowed(tile) {
+            self.announcement = self.texts().not_allowed.to_string();
+            return;
+        }
+
+        self.act(pyc::Action::Tile(tile as u8));
+        self.announcement = format!("{} {}", self.announcement, lesson.done);
+
+        if let Some(tutorial) = self.tutorial.as_mut() {
+            tutorial.done = true;
+        }
+    }
+
+    /// Updates the tutorial based on a [`TutorialMsg`].
+    pub(crate) fn update_tutorial(&mut self, msg: TutorialMsg) -> ShouldRender {
+        let tutorial = match self.tutorial.as_mut() {
+            Some(tutorial) => tutorial,
+            None => return false,
+        };
+
+        match msg {
+            TutorialMsg::Previous => tutorial.lesson = tutorial.lesson.saturating_sub(1),
+            TutorialMsg::Next => tutorial.lesson = (tutorial.lesson + 1).min(LESSONS.len() - 1),
+            TutorialMsg::Retry => {}
+        }
+
+        self.start_lesson();
+        true
+    }
+
+    /// [`Html`] text and controls of the lesson, instead of the actions.
+    pub(crate) fn tutorial_view(&self) -> Html {
+        let tutorial = match &self.tutorial {
+            Some(tutorial) => tutorial,
+            None => return html! {},
+        };
+
+        let texts = self.texts();
+        let lesson = &texts.lessons[tutorial.lesson];
+        let last = LESSONS.len() - 1;
+
+        html! {
+            <div class="tutorial">
+                <span class="tutorial-title">{
+                    fill(texts.lesson_title, &[&(tutorial.lesson + 1), &(last + 1), &lesson.title])
+                }</span>
+                <p class="tutorial-text">{ lesson.text }</p>
+                {
+                    if tutorial.done {
+                        html! { <p class="tutorial-text tutorial-done">{ lesson.done }</p> }
+                    } else {
+                        html! {}
+                    }
+                }
+                <button
+                    class="action-button"
+                    disabled={ tutorial.lesson == 0 }
+                    onclick=self.link.callback(|_| GameMsg::Tutorial(TutorialMsg::Previous))
+                >{ texts.previous }</button>
+                <button
+                    class="action-button"
+                    disabled={ tutorial.done }
+                    onclick=self.link.callback(|_| GameMsg::Hint)
+                >{ texts.hint }</button>
+                <button
+                    class="action-button"
+                    disabled=!tutorial.done
+                    onclick=self.link.callback(|_| GameMsg::Tutorial(TutorialMsg::Retry))
+                >{ texts.retry }</button>
+                <button
+                    class="action-button"
+                    disabled={ tutorial.lesson == last }
+                    onclick=self.link.callback(|_| GameMsg::Tutorial(TutorialMsg::Next))
+                >{ texts.next }</button>
+            </div>
+        }
+    }
+}
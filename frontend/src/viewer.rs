@@ -0,0 +1,185 @@
+//! Step through a [`pyc::Record`] without touching the live board,
+//! and share games as links.
+
+use super::{a11y, clock, i18n::fill, GameMsg, Polycentrics};
+use backend::prelude as pyc;
+use yew::prelude::*;
+
+/// A record shown move by move, instead of the live board.
+pub(crate) struct Viewer {
+    pub record: pyc::Record,
+    // number of moves done on `board`
+    pub at: usize,
+    pub board: pyc::Board,
+}
+
+/// Messages of the [`Viewer`].
+pub(crate) enum ViewerMsg {
+    // view the moves of the live game
+    Open,
+    // show the board after this many moves
+    Seek(usize),
+    First,
+    Back,
+    Forward,
+    Last,
+    // play on from the shown move, dropping the ones after
+    Continue,
+    // back to the live board
+    Close,
+}
+
+impl Viewer {
+    /// View `record` after its last move.
+    pub(crate) fn new(record: pyc::Record) -> Self {
+        let at = record.moves.len();
+        // shared records are decoded and the own one was played, both replay
+        let board = record.replay().unwrap();
+
+        Self { record, at, board }
+    }
+
+    /// Show the board after `at` moves.
+    // replayed from the start, because boards can't step back
+    fn seek(&mut self, at: usize) {
+        self.at = at.min(self.record.moves.len());
+        // the first moves of a record which replays
+        self.board = pyc::Record {
+            moves: self.record.moves[..self.at].to_vec(),
+            ..self.record.clone()
+        }
+        .replay()
+        .unwrap();
+    }
+}
+
+/// The game of the link the page was opened with, if any.
+pub(crate) fn shared_record() -> Option<pyc::Record> {
+    let hash = yew::utils::window().location().hash().ok()?;
+    let code = hash.trim_start_matches('#');
+
+    if code.is_empty() {
+        return None;
+    }
+
+    pyc::Record::decode(code).ok()
+}
+
+impl Polycentrics {
+    /// The board to draw, the viewed one or else the live one.
+    pub(crate) fn shown(&self) -> &pyc::Board {
+        match &self.viewer {
+            Some(viewer) => &viewer.board,
+            None => &self.board,
+        }
+    }
+
+    /// Updates the viewer based on a [`ViewerMsg`].
+    pub(crate) fn update_viewer(&mut self, msg: ViewerMsg) -> ShouldRender {
+        if let ViewerMsg::Open = msg {
+            // the server holds the moves of online games
+            if !self.online.is_connected() {
+                self.preview = None;
+                self.selected = None;
+                self.viewer = Some(Viewer::new(self.record.clone()));
+            }
+            return true;
+        }
+
+        let viewer = match self.viewer.as_mut() {
+            Some(viewer) => viewer,
+            None => return false,
+        };
+
+        match msg {
+            ViewerMsg::Open => {}
+            ViewerMsg::Seek(at) => viewer.seek(at),
+            ViewerMsg::First => viewer.seek(0),
+            ViewerMsg::Back => viewer.seek(viewer.at.saturating_sub(1)),
+            ViewerMsg::Forward => viewer.seek(viewer.at + 1),
+            ViewerMsg::Last => viewer.seek(viewer.record.moves.len()),
+            ViewerMsg::Continue => {
+                if let Some(viewer) = self.viewer.take() {
+                    self.cancel_thinking();
+                    self.opponent_task = None;
+
+                    self.record = viewer.record;
+                    self.record.moves.truncate(viewer.at);
+                    self.board = viewer.board;
+                    self.hint = None;
+                    // a new game, the viewed one stays as it was
+                    self.save_id = clock::now();
+
+                    self.save();
+                    self.schedule_opponent();
+                }
+            }
+            ViewerMsg::Close => self.viewer = None,
+        }
+
+        true
+    }
+
+    /// The last tile and its captures are highlighted, while viewing.
+    pub(crate) fn is_latest(&self, step: u8) -> bool {
+        self.viewer.is_some() && self.shown().step.checked_sub(1) == Some(step)
+    }
+
+    /// Put the code of the game into the address bar, to copy it from there.
+    pub(crate) fn share(&mut self) {
+        let code = self.record.encode();
+
+        if yew::utils::window().location().set_hash(&code).is_ok() {
+            self.announcement = self.texts().shared.to_string();
+        }
+    }
+
+    /// [`Html`] controls of the viewer, instead of the tile pad.
+    pub(crate) fn viewer_view(&self) -> Html {
+        let viewer = match &self.viewer {
+            Some(viewer) => viewer,
+            None => return html! {},
+        };
+
+        let last = viewer.record.moves.len();
+        let texts = self.texts();
+        let button = |text: &str, msg: fn() -> ViewerMsg, disabled: bool| {
+            html! {
+                <button
+                    class="action-button"
+                    disabled=disabled
+                    onclick=self.link.callback(move |_| GameMsg::Viewer(msg()))
+                >{ text }</button>
+            }
+        };
+
+        html! {
+            <div class="viewer">
+                <label class="viewer-position">
+                    { fill(texts.viewer_position, &[&viewer.board.step, &viewer.at, &last]) }{ " " }
+                    <input
+                        type="range"
+                        min="0"
+                        max=last.to_string()
+                        value=viewer.at.to_string()
+                        // parsing can only fail for a broken input
+                        oninput=self.link.callback(|e: InputData| {
+                            GameMsg::Viewer(ViewerMsg::Seek(e.value.parse().unwrap_or_default()))
+                        })
+                    />
+                </label>
+                { button(texts.first, || ViewerMsg::First, viewer.at == 0) }
+                { button(texts.back, || ViewerMsg::Back, viewer.at == 0) }
+                { button(texts.forward, || ViewerMsg::Forward, viewer.at == last) }
+                { button(texts.last, || ViewerMsg::Last, viewer.at == last) }
+                {
+                    match a11y::result_label(texts, &viewer.board.state) {
+                        Some(result) => html! { <span class="viewer-result">{ result }</span> },
+                        None => button(texts.continue_here, || ViewerMsg::Continue, false),
+                    }
+                }
+                { button(texts.close, || ViewerMsg::Close, false) }
+            </div>
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Web Worker running the [`pyc::Search`] off the main thread.
+
+use backend::prelude as pyc;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use yew::{
+    agent::{Agent, AgentLink, HandlerId, Public},
+    services::{timeout::TimeoutTask, TimeoutService},
+};
+
+/// Requests to the [`SearchWorker`].
+#[derive(Serialize, Deserialize)]
+pub enum WorkerInput {
+    /// Search the best tile, deepening one step at a time up to `depth`.
+    /// Replaces a running search.
+    Search { id: u32, board: pyc::Board, depth: u8 },
+    /// Stop the search of `id`, if it still runs.
+    Cancel { id: u32 },
+}
+
+/// Answers of the [`SearchWorker`], tagged with the id of their search.
+#[derive(Serialize, Deserialize)]
+pub enum WorkerOutput {
+    /// Best tile so far, found at `depth`.
+    Progress {
+        id: u32,
+        depth: u8,
+        tile: usize,
+        eval: pyc::Eval,
+    },
+    /// The search reached its depth, `None` if there were no options.
+    Done { id: u32, tile: Option<usize> },
+}
+
+/// Agent running one search at a time in its own thread.
+pub struct SearchWorker {
+    link: AgentLink<Self>,
+    job: Option<Job>,
+    // wakeup for the next depth, dropping it cancels the wakeup
+    next: Option<TimeoutTask>,
+}
+
+/// A running search.
+struct Job {
+    who: HandlerId,
+    id: u32,
+    board: pyc::Board,
+    // next depth to search
+    depth: u8,
+    // last depth to search
+    max: u8,
+    best: Option<usize>,
+}
+
+/// Internal messages of the [`SearchWorker`].
+pub enum WorkerMsg {
+    // search the next depth of the job
+    Deepen,
+}
+
+impl Agent for SearchWorker {
+    type Reach = Public<Self>;
+    type Message = WorkerMsg;
+    type Input = WorkerInput;
+    type Output = WorkerOutput;
+
+    fn create(link: AgentLink<Self>) -> Self {
+        Self {
+            link,
+            job: None,
+            next: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) {
+        match msg {
+            WorkerMsg::Deepen => {
+                let job = match self.job.as_mut() {
+                    Some(job) => job,
+                    None => return,
+                };
+
+                let search = pyc::Search { depth: job.depth };
+
+                if let Some((tile, eval)) = search.best(&job.board) {
+                    job.best = Some(tile);
+
+                    self.link.respond(
+                        job.who,
+                        WorkerOutput::Progress {
+                            id: job.id,
+                            depth: job.depth,
+                            tile,
+                            eval,
+                        },
+                    );
+                }
+
+                if job.depth >= job.max || job.best.is_none() {
+                    self.link.respond(
+                        job.who,
+                        WorkerOutput::Done {
+                            id: job.id,
+                            tile: job.best,
+                        },
+                    );
+                    self.job = None;
+                } else {
+                    job.depth += 1;
+                    self.deepen();
+                }
+            }
+        }
+    }
+
+    fn handle_input(&mut self, msg: Self::Input, who: HandlerId) {
+        match msg {
+            WorkerInput::Search { id, board, depth } => {
+                self.job = Some(Job {
+                    who,
+                    id,
+                    board,
+                    depth: 0,
+                    max: depth,
+                    best: None,
+                });
+                self.deepen();
+            }
+            WorkerInput::Cancel { id } => {
+                if matches!(&self.job, Some(job) if job.id == id && job.who == who) {
+                    self.job = None;
+                    self.next = None;
+                }
+            }
+        }
+    }
+
+    fn disconnected(&mut self, who: HandlerId) {
+        // nobody would receive the result
+        if matches!(&self.job, Some(job) if job.who == who) {
+            self.job = None;
+            self.next = None;
+        }
+    }
+
+    fn name_of_resource() -> &'static str {
+        // built by trunk from `src/bin/worker.rs`, see `index.html`
+        "worker.js"
+    }
+}
+
+impl SearchWorker {
+    /// Search the next depth after the messages waiting in the event loop,
+    /// so a cancel or a new search is handled in between.
+    // not `link.send_message`, because it runs right away
+    fn deepen(&mut self) {
+        self.next = Some(TimeoutService::spawn(
+            Duration::from_millis(0),
+            self.link.callback(|_| WorkerMsg::Deepen),
+        ));
+    }
+}
@@ -0,0 +1,173 @@
+// `tungstenite::Error` is large, but only returned once per connection
+#![allow(clippy::result_large_err)]
+
+use crate::games::{ClientId, Games, Outbox};
+use backend::prelude as pyc;
+use pyc::{Action, ClientMsg, Player, ServerMsg};
+use std::{
+    io,
+    net::TcpStream,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use tungstenite::{error::ProtocolError, Error, Message, WebSocket};
+
+// how long to wait for client messages before sending queued ones
+const POLL: Duration = Duration::from_millis(20);
+
+/// Serve one client until it disconnects.
+pub(crate) fn handle(stream: TcpStream, games: Arc<Mutex<Games>>) -> tungstenite::Result<()> {
+    let mut ws = tungstenite::accept(stream).map_err(|err| match err {
+        tungstenite::HandshakeError::Failure(err) => err,
+        tungstenite::HandshakeError::Interrupted(_) => Error::Io(io::ErrorKind::WouldBlock.into()),
+    })?;
+
+    // after the handshake, so it can't be interrupted
+    ws.get_ref().set_read_timeout(Some(POLL))?;
+
+    let (outbox, inbox) = mpsc::channel();
+    let mut client = Client {
+        id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        role: Role::Idle,
+        outbox,
+    };
+
+    let result = serve(&mut ws, &mut client, &inbox, &games);
+
+    // leave the game, no matter how the connection ended
+    match client.role {
+        Role::Idle => {}
+        Role::Seated(game, player) => games.lock().unwrap().leave(game, player, Instant::now()),
+        Role::Spectating(game) => games.lock().unwrap().unspectate(game, client.id),
+    }
+
+    result
+}
+
+// ids of connections, unique for the lifetime of the server
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The state of a connection.
+struct Client {
+    id: ClientId,
+    role: Role,
+    outbox: Outbox,
+}
+
+/// What a connection does in a game.
+#[derive(Clone, Copy)]
+enum Role {
+    Idle,
+    Seated(u32, Player),
+    Spectating(u32),
+}
+
+fn serve(
+    ws: &mut WebSocket<TcpStream>,
+    client: &mut Client,
+    inbox: &mpsc::Receiver<ServerMsg>,
+    games: &Mutex<Games>,
+) -> tungstenite::Result<()> {
+    loop {
+        match ws.read() {
+            Ok(Message::Text(text)) => {
+                let result = match serde_json::from_str(&text) {
+                    Ok(msg) => client.receive(msg, &mut games.lock().unwrap()),
+                    Err(err) => Err(format!("invalid message: {}", err)),
+                };
+
+                if let Err(message) = result {
+                    let _ = client.outbox.send(ServerMsg::Error { message });
+                }
+            }
+            // pings are answered by `tungstenite`, binary frames are not part of the protocol
+            Ok(_) => {}
+            Err(Error::Io(err))
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) => {}
+            // browsers often close the tab instead of the socket
+            Err(Error::ConnectionClosed)
+            | Err(Error::AlreadyClosed)
+            | Err(Error::Protocol(ProtocolError::ResetWithoutClosingHandshake)) => return Ok(()),
+            Err(err) => return Err(err),
+        }
+
+        for msg in inbox.try_iter() {
+            // serializing our own types can't fail
+            let text = serde_json::to_string(&msg).unwrap();
+            ws.send(Message::Text(text))?;
+        }
+    }
+}
+
+impl Client {
+    /// Apply a message of the client to the games.
+    fn receive(&mut self, msg: ClientMsg, games: &mut Games) -> Result<(), String> {
+        match (msg, self.role) {
+            (ClientMsg::Lobby, _) => {
+                let _ = self.outbox.send(ServerMsg::Lobby {
+                    games: games.lobby(),
+                });
+            }
+            (
+                ClientMsg::Create {
+                    name,
+                    size,
+                    tiles,
+                    clock,
+                },
+                Role::Idle,
+            ) => {
+                let game = games.create(
+                    name,
+                    size,
+                    tiles,
+                    clock,
+                    self.outbox.clone(),
+                    Instant::now(),
+                )?;
+                self.role = Role::Seated(game, Player::Gamma);
+            }
+            (ClientMsg::Join { game, name, token }, Role::Idle) => {
+                let player = games.join(game, name, token, self.outbox.clone(), Instant::now())?;
+                self.role = Role::Seated(game, player);
+            }
+            (ClientMsg::Spectate { game }, Role::Idle) => {
+                games.spectate(game, self.id, self.outbox.clone(), Instant::now())?;
+                self.role = Role::Spectating(game);
+            }
+            (ClientMsg::Tile { tile }, Role::Seated(game, player)) => {
+                games.tile(game, player, &tile, Instant::now())?;
+            }
+            (ClientMsg::Resign, Role::Seated(game, player)) => {
+                games.act(game, player, Action::Resign(player), Instant::now())?;
+            }
+            (ClientMsg::OfferDraw, Role::Seated(game, player)) => {
+                games.act(game, player, Action::OfferDraw(player), Instant::now())?;
+            }
+            (ClientMsg::AcceptDraw, Role::Seated(game, player)) => {
+                games.act(game, player, Action::AcceptDraw(player), Instant::now())?;
+            }
+            (ClientMsg::DeclineDraw, Role::Seated(game, player)) => {
+                games.act(game, player, Action::DeclineDraw(player), Instant::now())?;
+            }
+            (
+                ClientMsg::Tile { .. }
+                | ClientMsg::Resign
+                | ClientMsg::OfferDraw
+                | ClientMsg::AcceptDraw
+                | ClientMsg::DeclineDraw,
+                _,
+            ) => return Err("not seated".to_string()),
+            (_, Role::Seated(..)) => return Err("already seated".to_string()),
+            (_, Role::Spectating(..)) => return Err("already spectating".to_string()),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,492 @@
+use backend::prelude as pyc;
+use pyc::{Action, Clock, GameInfo, Millis, Player, ServerMsg, State, TimeControl};
+use std::{
+    collections::HashMap,
+    sync::mpsc::Sender,
+    time::{Duration, Instant},
+};
+
+/// Messages waiting to be sent by a connection.
+pub(crate) type Outbox = Sender<ServerMsg>;
+
+/// Unique id of a connection.
+pub(crate) type ClientId = u64;
+
+/// How long an abandoned seat of a running game is held for its player.
+pub(crate) const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A player sitting at a [`Game`].
+pub(crate) struct Seat {
+    name: String,
+    // reclaims the seat once abandoned, only known to its player
+    token: String,
+    // `None` while the seat is abandoned
+    outbox: Option<Outbox>,
+    // when the player left, for the timeout
+    left: Option<Instant>,
+}
+
+/// A game hosted by the server, the only source of truth for its [`pyc::Board`].
+pub(crate) struct Game {
+    // the history sent to spectators
+    record: pyc::Record,
+    board: pyc::Board,
+    // `None` for untimed games
+    clock: Option<Clock>,
+    // the clock counts from here, it can't hold an `Instant`
+    created: Instant,
+    // indexed by `seat_index`
+    seats: [Option<Seat>; 2],
+    // both seats were taken once, forfeited seats stay empty
+    started: bool,
+    spectators: HashMap<ClientId, Outbox>,
+}
+
+/// Session manager holding all games of the server.
+#[derive(Default)]
+pub(crate) struct Games {
+    // ids are never reused, so late messages can't reach a new game
+    next: u32,
+    games: HashMap<u32, Game>,
+}
+
+impl Games {
+    /// Summaries of all games, ordered by id.
+    pub fn lobby(&self) -> Vec<GameInfo> {
+        let mut games: Vec<GameInfo> = self
+            .games
+            .iter()
+            .map(|(id, game)| {
+                let [gamma, delta] = &game.seats;
+
+                GameInfo {
+                    game: *id,
+                    size: game.record.size,
+                    tiles: game.record.tiles,
+                    clock: game.clock.as_ref().map(|clock| clock.control),
+                    gamma: gamma.as_ref().map(|seat| seat.name.clone()),
+                    delta: delta.as_ref().map(|seat| seat.name.clone()),
+                    spectators: game.spectators.len() as u32,
+                    step: game.board.step,
+                    open: game.is_open(),
+                }
+            })
+            .collect();
+
+        games.sort_by_key(|info| info.game);
+        games
+    }
+
+    /// Host a new game and seat its creator as [`Player::Gamma`].
+    pub fn create(
+        &mut self,
+        name: String,
+        size: u8,
+        tiles: pyc::TileSet,
+        clock: Option<TimeControl>,
+        outbox: Outbox,
+        now: Instant,
+    ) -> Result<u32, String> {
+        if !pyc::SIZES.contains(&size) {
+            return Err(format!(
+                "board size must be within {} and {}",
+                pyc::SIZES.start(),
+                pyc::SIZES.end()
+            ));
+        }
+
+        let seat = Seat::new(name, outbox)?;
+
+        self.next += 1;
+        let id = self.next;
+
+        let record = pyc::Record {
+            tiles,
+            ..pyc::Record::with_size(size)
+        };
+
+        seat.seated(id, Player::Gamma, &record);
+
+        self.games.insert(
+            id,
+            Game {
+                board: record.start(),
+                record,
+                clock: clock.map(Clock::new),
+                created: now,
+                seats: [Some(seat), None],
+                started: false,
+                spectators: HashMap::new(),
+            },
+        );
+
+        Ok(id)
+    }
+
+    /// Take the free seat of a game, or return to the abandoned seat of `token`.
+    /// The game starts once both seats are taken.
+    pub fn join(
+        &mut self,
+        id: u32,
+        name: String,
+        token: Option<String>,
+        outbox: Outbox,
+        now: Instant,
+    ) -> Result<Player, String> {
+        let game = self.get_mut(id)?;
+
+        // a player returning to the seat it abandoned
+        if let Some(token) = token {
+            let player = [Player::Gamma, Player::Delta]
+                .iter()
+                .copied()
+                .find(|player| {
+                    matches!(
+                        &game.seats[seat_index(*player)],
+                        Some(seat) if seat.outbox.is_none() && seat.token == token
+                    )
+                })
+                .ok_or_else(|| format!("no abandoned seat of yours in game {}", id))?;
+
+            let _ = outbox.send(ServerMsg::Seated {
+                game: id,
+                player,
+                token,
+                record: game.record.clone(),
+            });
+            let _ = outbox.send(game.start());
+            for msg in game.state(now) {
+                let _ = outbox.send(msg);
+            }
+
+            // the name stays the one the opponent saw at the start
+            if let Some(seat) = game.seats[seat_index(player)].as_mut() {
+                seat.outbox = Some(outbox);
+                seat.left = None;
+            }
+            game.broadcast(ServerMsg::Returned { player });
+
+            return Ok(player);
+        }
+
+        // seats freed after the start are not offered again
+        if !game.is_open() {
+            return Err(format!("game {} is not open", id));
+        }
+
+        let player = match game.seats {
+            [None, _] => Player::Gamma,
+            [_, None] => Player::Delta,
+            _ => return Err(format!("game {} is full", id)),
+        };
+
+        let seat = Seat::new(name, outbox)?;
+        seat.seated(id, player, &game.record);
+        game.seats[seat_index(player)] = Some(seat);
+
+        if game.seats.iter().all(Option::is_some) {
+            game.started = true;
+            game.broadcast(game.start());
+            game.broadcast_state(now);
+        }
+
+        Ok(player)
+    }
+
+    /// Watch a game, starting with its history.
+    pub fn spectate(
+        &mut self,
+        id: u32,
+        client: ClientId,
+        outbox: Outbox,
+        now: Instant,
+    ) -> Result<(), String> {
+        let game = self.get_mut(id)?;
+
+        let _ = outbox.send(ServerMsg::Spectating {
+            game: id,
+            record: game.record.clone(),
+        });
+
+        if game.is_started() {
+            let _ = outbox.send(game.start());
+        }
+
+        for msg in game.state(now) {
+            let _ = outbox.send(msg);
+        }
+
+        game.spectators.insert(client, outbox);
+        Ok(())
+    }
+
+    /// Set a tile for `player`, chosen by index or notation, see [`Games::act`].
+    pub fn tile(
+        &mut self,
+        id: u32,
+        player: Player,
+        tile: &pyc::TileChoice,
+        now: Instant,
+    ) -> Result<(), String> {
+        let action = tile
+            .action(&self.get_mut(id)?.board)
+            .map_err(|err| err.to_string())?;
+
+        self.act(id, player, action, now)
+    }
+
+    /// Do an [`Action`] for `player`, tiles only on its turn of a started game.
+    pub fn act(
+        &mut self,
+        id: u32,
+        player: Player,
+        action: Action,
+        now: Instant,
+    ) -> Result<(), String> {
+        let game = self.get_mut(id)?;
+
+        if !game.is_started() {
+            return Err("waiting for an opponent".to_string());
+        }
+
+        // the reaper might not have noticed yet
+        if game.flag(now) {
+            return Err("time is up".to_string());
+        }
+
+        if matches!(action, Action::Tile(_)) && game.board.active != player {
+            return Err("not your turn".to_string());
+        }
+
+        let step = game.board.step;
+        let before = score(&game.board, player);
+
+        let millis = game.millis(now);
+        match game.clock.as_mut() {
+            Some(clock) => clock.act(&mut game.board, action, millis),
+            None => game.board.act(action),
+        }
+        .map_err(|err| err.to_string())?;
+
+        game.record.moves.push(action);
+
+        game.broadcast(match action {
+            Action::Tile(tile) => ServerMsg::Step {
+                step,
+                player,
+                tile,
+                captured: score(&game.board, player) - before,
+            },
+            _ => ServerMsg::Action { action },
+        });
+        game.broadcast_state(now);
+
+        Ok(())
+    }
+
+    /// A seated player disconnected. Its seat is held until [`TIMEOUT`]
+    /// if the game is running, otherwise it is freed right away.
+    pub fn leave(&mut self, id: u32, player: Player, now: Instant) {
+        if let Some(game) = self.games.get_mut(&id) {
+            let running = game.is_started() && matches!(game.board.state, State::Pending);
+
+            match game.seats[seat_index(player)].as_mut() {
+                Some(seat) if running => {
+                    seat.outbox = None;
+                    seat.left = Some(now);
+
+                    game.broadcast(ServerMsg::Abandoned {
+                        player,
+                        seconds: TIMEOUT.as_secs(),
+                    });
+                }
+                _ => game.seats[seat_index(player)] = None,
+            }
+
+            if game.seats.iter().all(Option::is_none) {
+                self.games.remove(&id);
+            }
+        }
+    }
+
+    /// A spectator disconnected.
+    pub fn unspectate(&mut self, id: u32, client: ClientId) {
+        if let Some(game) = self.games.get_mut(&id) {
+            game.spectators.remove(&client);
+        }
+    }
+
+    /// Forfeit seats abandoned for longer than [`TIMEOUT`], end games
+    /// whose clock ran out and close empty games.
+    // `now` is passed in, so the timeout does not depend on the wall-clock
+    pub fn reap(&mut self, now: Instant) {
+        for game in self.games.values_mut() {
+            game.flag(now);
+
+            for player in [Player::Gamma, Player::Delta].iter().copied() {
+                let expired = matches!(
+                    &game.seats[seat_index(player)],
+                    Some(Seat { left: Some(left), .. }) if now.duration_since(*left) >= TIMEOUT
+                );
+
+                if expired {
+                    game.seats[seat_index(player)] = None;
+
+                    if let State::Pending = game.board.state {
+                        game.board.state = State::Victory(player.opponent());
+
+                        let millis = game.millis(now);
+                        if let Some(clock) = game.clock.as_mut() {
+                            clock.stop(millis);
+                        }
+                        game.broadcast_state(now);
+                    }
+                }
+            }
+        }
+
+        self.games
+            .retain(|_, game| game.seats.iter().any(Option::is_some));
+    }
+
+    fn get_mut(&mut self, id: u32) -> Result<&mut Game, String> {
+        self.games
+            .get_mut(&id)
+            .ok_or_else(|| format!("no game {}", id))
+    }
+}
+
+impl Seat {
+    fn new(name: String, outbox: Outbox) -> Result<Self, String> {
+        Ok(Self {
+            name,
+            token: token()?,
+            outbox: Some(outbox),
+            left: None,
+        })
+    }
+
+    /// Tell the player it took the seat, with the token to take it back.
+    fn seated(&self, game: u32, player: Player, record: &pyc::Record) {
+        if let Some(outbox) = &self.outbox {
+            let _ = outbox.send(ServerMsg::Seated {
+                game,
+                player,
+                token: self.token.clone(),
+                record: record.clone(),
+            });
+        }
+    }
+}
+
+impl Game {
+    /// Both seats were taken, even if they were abandoned or forfeited since.
+    fn is_started(&self) -> bool {
+        self.started
+    }
+
+    /// Waiting for an opponent, the only time a new player may join.
+    fn is_open(&self) -> bool {
+        !self.started && matches!(self.board.state, State::Pending)
+    }
+
+    /// The start message of a started game.
+    fn start(&self) -> ServerMsg {
+        let name = |player| {
+            self.seats[seat_index(player)]
+                .as_ref()
+                .map(|seat: &Seat| seat.name.clone())
+                .unwrap_or_default()
+        };
+
+        ServerMsg::Start {
+            gamma: name(Player::Gamma),
+            delta: name(Player::Delta),
+        }
+    }
+
+    /// Milliseconds since the game was created, the time of its [`Clock`].
+    fn millis(&self, now: Instant) -> Millis {
+        now.duration_since(self.created).as_millis() as Millis
+    }
+
+    /// The board and the time left, as sent after every step.
+    fn state(&self, now: Instant) -> Vec<ServerMsg> {
+        let mut msgs = vec![ServerMsg::Update {
+            board: self.board.clone(),
+        }];
+
+        if let Some(clock) = &self.clock {
+            let millis = self.millis(now);
+
+            msgs.push(ServerMsg::Clock {
+                control: clock.control,
+                gamma: clock.left(Player::Gamma, millis),
+                delta: clock.left(Player::Delta, millis),
+                running: clock.running.map(|(player, _)| player),
+            });
+        }
+
+        msgs
+    }
+
+    /// Send the state to every connected seat and spectator.
+    fn broadcast_state(&self, now: Instant) {
+        for msg in self.state(now) {
+            self.broadcast(msg);
+        }
+    }
+
+    /// End the game if the time of the active player ran out.
+    /// Returns `true` if it did.
+    fn flag(&mut self, now: Instant) -> bool {
+        let millis = self.millis(now);
+
+        let flagged = match self.clock.as_mut() {
+            Some(clock) => clock.check(&mut self.board, millis),
+            None => false,
+        };
+
+        if flagged {
+            self.broadcast_state(now);
+        }
+
+        flagged
+    }
+
+    /// Send a message to every connected seat and spectator.
+    fn broadcast(&self, msg: ServerMsg) {
+        let seats = self
+            .seats
+            .iter()
+            .flatten()
+            .filter_map(|seat| seat.outbox.as_ref());
+
+        for outbox in seats.chain(self.spectators.values()) {
+            let _ = outbox.send(msg.clone());
+        }
+    }
+}
+
+/// 128 random bits in hex, too many to guess a seat.
+// from the OS, as the keys of `RandomState` are only seeded once per thread
+fn token() -> Result<String, String> {
+    let mut bytes = [0; 16];
+    getrandom::getrandom(&mut bytes).map_err(|err| format!("no token for the seat: {}", err))?;
+
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+pub(crate) fn seat_index(player: Player) -> usize {
+    match player {
+        Player::Gamma => 0,
+        Player::Delta => 1,
+    }
+}
+
+fn score(board: &pyc::Board, player: Player) -> u64 {
+    match player {
+        Player::Gamma => board.score.gamma,
+        Player::Delta => board.score.delta,
+    }
+}
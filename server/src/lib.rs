@@ -0,0 +1,43 @@
+//! # Polycentrics Server
+//! Hosts games authoritatively over WebSockets, see `PROTOCOL.md`.
+
+mod connection;
+mod games;
+
+use games::Games;
+use std::{
+    io,
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+// how often abandoned seats and clocks are checked for their timeout
+const REAP: Duration = Duration::from_millis(100);
+
+/// Accept connections on `listener` until it fails, one thread per connection.
+// a listener and not an address, so tests can bind to a free loopback port
+pub fn serve(listener: TcpListener) -> io::Result<()> {
+    let games = Arc::new(Mutex::new(Games::default()));
+
+    // forfeit abandoned seats and flag clocks, even if no client sends anything
+    let reaper = Arc::clone(&games);
+    thread::spawn(move || loop {
+        thread::sleep(REAP);
+        reaper.lock().unwrap().reap(Instant::now());
+    });
+
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let games = Arc::clone(&games);
+
+        thread::spawn(move || {
+            if let Err(err) = connection::handle(stream, games) {
+                eprintln!("connection failed: {}", err);
+            }
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use std::{env, net::TcpListener, process};
+
+// loopback only by default, pass `0.0.0.0:<port>` to play over the network
+const ADDR: &str = "127.0.0.1:9001";
+
+fn main() {
+    let addr = env::args().nth(1).unwrap_or_else(|| ADDR.to_string());
+
+    let listener = match TcpListener::bind(&addr) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("{}: {}", addr, err);
+            process::exit(1);
+        }
+    };
+
+    println!("listening on ws://{}", addr);
+
+    if let Err(err) = polycentrics_server::serve(listener) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
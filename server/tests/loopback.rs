@@ -0,0 +1,216 @@
+//! Play through the server over loopback connections, with the JSON of `PROTOCOL.md`.
+
+use serde_json::Value;
+use std::{
+    net::{SocketAddr, TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+use tungstenite::{Message, WebSocket};
+
+// long enough for a slow CI machine, short enough to fail instead of hanging
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Start a server on a free port of the loopback interface.
+fn serve() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    thread::spawn(move || polycentrics_server::serve(listener));
+    addr
+}
+
+/// One client connection.
+struct Client(WebSocket<TcpStream>);
+
+impl Client {
+    fn connect(addr: SocketAddr) -> Self {
+        let stream = TcpStream::connect(addr).unwrap();
+        stream.set_read_timeout(Some(TIMEOUT)).unwrap();
+
+        let (ws, _) = tungstenite::client(format!("ws://{}", addr), stream).unwrap();
+        Self(ws)
+    }
+
+    fn send(&mut self, json: &str) {
+        self.0.send(Message::Text(json.to_string())).unwrap();
+    }
+
+    /// The next message, which must be of type `kind`.
+    fn recv(&mut self, kind: &str) -> Value {
+        loop {
+            if let Message::Text(text) = self.0.read().unwrap() {
+                let msg: Value = serde_json::from_str(&text).unwrap();
+                assert_eq!(msg["type"], kind, "{}", text);
+                return msg;
+            }
+        }
+    }
+
+    /// Close the connection and wait for the server to answer.
+    fn close(mut self) {
+        self.0.close(None).unwrap();
+        while self.0.read().is_ok() {}
+    }
+}
+
+#[test]
+fn create_join_tile_broadcast() {
+    let addr = serve();
+
+    let mut ann = Client::connect(addr);
+    ann.send(r#"{"type":"create","name":"Ann","size":11}"#);
+    let seated = ann.recv("seated");
+    assert_eq!(seated["player"], "Gamma");
+    assert_eq!(seated["record"]["size"], 11);
+    let game = seated["game"].clone();
+
+    let mut bob = Client::connect(addr);
+    bob.send(r#"{"type":"lobby"}"#);
+    let lobby = bob.recv("lobby");
+    assert_eq!(lobby["games"][0]["game"], game);
+    assert_eq!(lobby["games"][0]["gamma"], "Ann");
+    assert_eq!(lobby["games"][0]["open"], true);
+
+    bob.send(&format!(
+        r#"{{"type":"join","game":{},"name":"Bob"}}"#,
+        game
+    ));
+    assert_eq!(bob.recv("seated")["player"], "Delta");
+
+    for client in [&mut ann, &mut bob].iter_mut() {
+        let start = client.recv("start");
+        assert_eq!(start["gamma"], "Ann");
+        assert_eq!(start["delta"], "Bob");
+        assert_eq!(client.recv("update")["board"]["active"], "Gamma");
+    }
+
+    // only on your turn
+    bob.send(r#"{"type":"tile","tile":0}"#);
+    bob.recv("error");
+
+    ann.send(r#"{"type":"tile","tile":3}"#);
+    for client in [&mut ann, &mut bob].iter_mut() {
+        let step = client.recv("step");
+        assert_eq!(step["player"], "Gamma");
+        assert_eq!(step["tile"], 3);
+        assert_eq!(client.recv("update")["board"]["active"], "Delta");
+    }
+
+    let mut eve = Client::connect(addr);
+    eve.send(&format!(r#"{{"type":"spectate","game":{}}}"#, game));
+    assert_eq!(eve.recv("spectating")["record"]["moves"][0]["Tile"], 3);
+    eve.recv("start");
+    eve.recv("update");
+
+    // tiles can also be chosen in notation
+    bob.send(r#"{"type":"tile","tile":"R4u"}"#);
+    bob.recv("error");
+    bob.send(r#"{"type":"tile","tile":"L1u"}"#);
+    for client in [&mut ann, &mut bob, &mut eve].iter_mut() {
+        assert_eq!(client.recv("step")["player"], "Delta");
+        client.recv("update");
+    }
+}
+
+#[test]
+fn finished_games_are_not_open() {
+    let addr = serve();
+
+    let mut ann = Client::connect(addr);
+    ann.send(r#"{"type":"create","name":"Ann","size":5}"#);
+    let game = ann.recv("seated")["game"].clone();
+
+    let mut bob = Client::connect(addr);
+    bob.send(&format!(
+        r#"{{"type":"join","game":{},"name":"Bob"}}"#,
+        game
+    ));
+    bob.recv("seated");
+
+    ann.send(r#"{"type":"resign"}"#);
+    for client in [&mut ann, &mut bob].iter_mut() {
+        client.recv("start");
+        client.recv("update");
+        client.recv("action");
+        assert_eq!(client.recv("update")["board"]["state"]["Victory"], "Delta");
+    }
+
+    // the seat of a finished game is freed right away
+    bob.close();
+
+    let mut carol = Client::connect(addr);
+    let mut lobby;
+    loop {
+        carol.send(r#"{"type":"lobby"}"#);
+        lobby = carol.recv("lobby");
+        if lobby["games"][0]["delta"].is_null() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    assert_eq!(lobby["games"][0]["open"], false);
+
+    carol.send(&format!(
+        r#"{{"type":"join","game":{},"name":"Carol"}}"#,
+        game
+    ));
+    carol.recv("error");
+}
+
+#[test]
+fn abandoned_seats_need_their_token() {
+    let addr = serve();
+
+    let mut ann = Client::connect(addr);
+    ann.send(r#"{"type":"create","name":"Ann","size":5}"#);
+    let game = ann.recv("seated")["game"].clone();
+
+    let mut bob = Client::connect(addr);
+    bob.send(&format!(
+        r#"{{"type":"join","game":{},"name":"Bob"}}"#,
+        game
+    ));
+    let token = bob.recv("seated")["token"].clone();
+    bob.recv("start");
+    bob.recv("update");
+
+    ann.recv("start");
+    ann.recv("update");
+    ann.send(r#"{"type":"tile","tile":1}"#);
+    ann.recv("step");
+    ann.recv("update");
+    bob.recv("step");
+    bob.recv("update");
+    bob.close();
+
+    assert_eq!(ann.recv("abandoned")["player"], "Delta");
+
+    // the name alone is not enough
+    let mut eve = Client::connect(addr);
+    eve.send(&format!(
+        r#"{{"type":"join","game":{},"name":"Bob"}}"#,
+        game
+    ));
+    eve.recv("error");
+    eve.send(&format!(
+        r#"{{"type":"join","game":{},"name":"Bob","token":"0"}}"#,
+        game
+    ));
+    eve.recv("error");
+
+    let mut bob = Client::connect(addr);
+    bob.send(&format!(
+        r#"{{"type":"join","game":{},"name":"Bob","token":{}}}"#,
+        game, token
+    ));
+    let seated = bob.recv("seated");
+    assert_eq!(seated["player"], "Delta");
+    assert_eq!(seated["token"], token);
+    // with the steps played while away
+    assert_eq!(seated["record"]["moves"][0]["Tile"], 1);
+    assert_eq!(bob.recv("start")["delta"], "Bob");
+    bob.recv("update");
+
+    assert_eq!(ann.recv("returned")["player"], "Delta");
+}